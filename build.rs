@@ -0,0 +1,101 @@
+// Declares the modules of the days whose feature is enabled, lists the others
+// and, with the embed-inputs feature, includes the inputs/NN.txt files found
+// at build time in the library.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+fn write_days(manifest_dir: &Path, out_dir: &Path) {
+    println!("cargo:rerun-if-changed=src/days");
+
+    let mut days = fs::read_dir(manifest_dir.join("src/days"))
+        .unwrap()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let name = path.file_stem()?.to_str()?.to_string();
+            let day_number = name.strip_prefix("day")?.parse::<u32>().ok()?;
+
+            Some((name, day_number, path))
+        })
+        .collect::<Vec<_>>();
+
+    days.sort();
+
+    let modules = days
+        .iter()
+        .map(|(name, _, path)| {
+            format!(
+                "#[cfg(feature = {name:?})]\n#[path = {:?}]\nmod {name};\n",
+                path.display().to_string()
+            )
+        })
+        .collect::<String>();
+    let compiled_out = days
+        .iter()
+        .filter(|(name, _, _)| {
+            env::var_os(format!("CARGO_FEATURE_{}", name.to_uppercase())).is_none()
+        })
+        .map(|(_, day_number, _)| format!("{day_number}, "))
+        .collect::<String>();
+
+    fs::write(out_dir.join("days.rs"), modules).unwrap();
+    fs::write(
+        out_dir.join("compiled_out.rs"),
+        format!("pub static COMPILED_OUT: &[u32] = &[{compiled_out}];\n"),
+    )
+    .unwrap();
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir = env::var("OUT_DIR").unwrap();
+
+    write_days(Path::new(&manifest_dir), Path::new(&out_dir));
+
+    if env::var_os("CARGO_FEATURE_EMBED_INPUTS").is_none() {
+        return;
+    }
+
+    println!("cargo:rerun-if-changed=inputs");
+
+    let dir = Path::new(&manifest_dir).join("inputs");
+    let mut inputs = fs::read_dir(&dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| {
+                    let path = entry.ok()?.path();
+                    let day_number = path
+                        .file_name()?
+                        .to_str()?
+                        .strip_suffix(".txt")?
+                        .parse::<u32>()
+                        .ok()?;
+
+                    Some((day_number, path))
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    inputs.sort();
+
+    let entries = inputs
+        .iter()
+        .map(|(day_number, path)| {
+            format!(
+                "    ({day_number}, include_str!({:?})),\n",
+                path.display().to_string()
+            )
+        })
+        .collect::<String>();
+    let out = Path::new(&out_dir).join("inputs.rs");
+
+    fs::write(
+        out,
+        format!("pub static INPUTS: &[(u32, &str)] = &[\n{entries}];\n"),
+    )
+    .unwrap();
+}
@@ -0,0 +1,59 @@
+/* Generated from capi/src/lib.rs by capi/build.rs, do not edit. */
+
+#ifndef ADVENT_2022_H
+#define ADVENT_2022_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/**
+ * Answers of a day, or the reason it failed in `error`, the other strings
+ * being null. They belong to the caller who releases them with
+ * `advent_free_answers`.
+ */
+typedef struct AdventAnswers {
+    char *part1;
+    char *part2;
+    char *error;
+    uint64_t duration_ns;
+} AdventAnswers;
+
+/**
+ * Writes the first `capacity` registered day numbers into `days` and returns
+ * how many days are registered.
+ *
+ * # Safety
+ *
+ * `days` must be valid for `capacity` writes, it may be null when `capacity`
+ * is 0.
+ */
+size_t advent_days(uint32_t *days, size_t capacity);
+
+/**
+ * Resolves `day` on the `length` bytes of `input`, which don't need a
+ * terminating null.
+ *
+ * # Safety
+ *
+ * `input` must be valid for `length` reads.
+ */
+AdventAnswers advent_solve(uint32_t day, const uint8_t *input, size_t length);
+
+/**
+ * Releases the strings of answers returned by `advent_solve`.
+ *
+ * # Safety
+ *
+ * `answers` must come from `advent_solve` and not have been released yet.
+ */
+void advent_free_answers(AdventAnswers answers);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,153 @@
+// Writes advent_2022.h into OUT_DIR from the `#[repr(C)]` structs and
+// `extern "C"` functions of src/lib.rs, which only use the few types mapped
+// below. The copy shipped next to the manifest is only updated by hand.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+const SOURCE: &str = "src/lib.rs";
+const HEADER: &str = "advent_2022.h";
+
+fn c_type(rust: &str) -> String {
+    let rust = rust.trim();
+
+    if let Some(pointee) = rust.strip_prefix("*const ") {
+        return format!("const {} *", c_type(pointee));
+    }
+    if let Some(pointee) = rust.strip_prefix("*mut ") {
+        return format!("{} *", c_type(pointee));
+    }
+
+    match rust {
+        "c_char" => "char",
+        "u8" => "uint8_t",
+        "u32" => "uint32_t",
+        "u64" => "uint64_t",
+        "i32" => "int32_t",
+        "usize" => "size_t",
+        "" => "void",
+        other => other,
+    }
+    .to_string()
+}
+
+// `type name` without a space after the star of pointers
+fn c_declaration(rust_type: &str, name: &str) -> String {
+    let c_type = c_type(rust_type);
+
+    if c_type.ends_with('*') {
+        format!("{c_type}{name}")
+    } else {
+        format!("{c_type} {name}")
+    }
+}
+
+fn c_comment(doc: &[String]) -> String {
+    if doc.is_empty() {
+        return String::new();
+    }
+
+    let mut comment = "/**\n".to_string();
+
+    for line in doc {
+        comment += &format!(" *{}{line}\n", if line.is_empty() { "" } else { " " });
+    }
+    comment + " */\n"
+}
+
+fn c_struct(name: &str, fields: &str) -> String {
+    let fields = fields
+        .split(',')
+        .filter_map(|f| f.split_once(':'))
+        .map(|(name, rust_type)| {
+            let name = name.trim().trim_start_matches("pub ");
+
+            format!("    {};\n", c_declaration(rust_type, name))
+        })
+        .collect::<String>();
+
+    format!("typedef struct {name} {{\n{fields}}} {name};\n")
+}
+
+fn c_function(signature: &str) -> String {
+    let (name, rest) = signature.split_once('(').unwrap();
+    let name = name.rsplit(' ').next().unwrap();
+    let (params, ret) = rest.rsplit_once(')').unwrap();
+    let params = params
+        .split(',')
+        .filter_map(|p| p.split_once(':'))
+        .map(|(name, rust_type)| c_declaration(rust_type, name.trim()))
+        .collect::<Vec<_>>();
+    let ret = ret.trim().trim_start_matches("->");
+
+    format!("{}({});\n", c_declaration(ret, name), params.join(", "))
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed={SOURCE}");
+
+    let source = fs::read_to_string(SOURCE).unwrap();
+    let mut lines = source.lines().map(str::trim);
+    let mut doc = vec![];
+    let mut items = vec![];
+
+    while let Some(line) = lines.next() {
+        if let Some(comment) = line.strip_prefix("///") {
+            doc.push(comment.trim().to_string());
+        } else if line.starts_with("#[") {
+            continue;
+        } else if line.starts_with("pub struct ") || line.contains("extern \"C\" fn ") {
+            let mut item = line.to_string();
+
+            while !item.contains('{') {
+                item += lines.next().unwrap();
+            }
+            if let Some(name) = line.strip_prefix("pub struct ") {
+                for field in lines.by_ref().take_while(|l| *l != "}") {
+                    item += field;
+                }
+
+                let (_, fields) = item.split_once('{').unwrap();
+
+                items.push(c_comment(&doc) + &c_struct(name.trim_end_matches(" {"), fields));
+            } else {
+                let (signature, _) = item.split_once('{').unwrap();
+
+                items.push(c_comment(&doc) + &c_function(signature));
+            }
+            doc.clear();
+        } else {
+            doc.clear();
+        }
+    }
+
+    let header = format!(
+        "/* Generated from capi/{SOURCE} by capi/build.rs, do not edit. */
+
+#ifndef ADVENT_2022_H
+#define ADVENT_2022_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern \"C\" {{
+#endif
+
+{}
+#ifdef __cplusplus
+}}
+#endif
+
+#endif
+",
+        items.join("\n")
+    );
+
+    fs::write(
+        Path::new(&env::var("OUT_DIR").unwrap()).join(HEADER),
+        header,
+    )
+    .unwrap();
+}
@@ -0,0 +1,103 @@
+//! C interface to the solvers, declared in the `advent_2022.h` header next to
+//! this crate's manifest. The build generates it into `OUT_DIR` and the tests
+//! check the copy shipped is up to date.
+
+use std::ffi::{c_char, CString};
+use std::ptr;
+use std::slice;
+
+/// Answers of a day, or the reason it failed in `error`, the other strings
+/// being null. They belong to the caller who releases them with
+/// `advent_free_answers`.
+#[repr(C)]
+pub struct AdventAnswers {
+    pub part1: *mut c_char,
+    pub part2: *mut c_char,
+    pub error: *mut c_char,
+    pub duration_ns: u64,
+}
+
+fn owned(s: String) -> *mut c_char {
+    CString::new(s.replace('\0', "")).unwrap().into_raw()
+}
+
+fn failed(error: String) -> AdventAnswers {
+    AdventAnswers {
+        part1: ptr::null_mut(),
+        part2: ptr::null_mut(),
+        error: owned(error),
+        duration_ns: 0,
+    }
+}
+
+/// Writes the first `capacity` registered day numbers into `days` and returns
+/// how many days are registered.
+///
+/// # Safety
+///
+/// `days` must be valid for `capacity` writes, it may be null when `capacity`
+/// is 0.
+#[no_mangle]
+pub unsafe extern "C" fn advent_days(days: *mut u32, capacity: usize) -> usize {
+    let registered = advent_2022::days();
+
+    if capacity > 0 {
+        let count = registered.len().min(capacity);
+
+        slice::from_raw_parts_mut(days, count).copy_from_slice(&registered[..count]);
+    }
+    registered.len()
+}
+
+/// Resolves `day` on the `length` bytes of `input`, which don't need a
+/// terminating null.
+///
+/// # Safety
+///
+/// `input` must be valid for `length` reads.
+#[no_mangle]
+pub unsafe extern "C" fn advent_solve(day: u32, input: *const u8, length: usize) -> AdventAnswers {
+    let input = if length == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(input, length)
+    };
+    let Ok(input) = String::from_utf8(input.to_vec()) else {
+        return failed("the input is not UTF-8".to_string());
+    };
+
+    match advent_2022::solve(day, input) {
+        Ok((part1, part2, duration)) => AdventAnswers {
+            part1: owned(part1),
+            part2: owned(part2),
+            error: ptr::null_mut(),
+            duration_ns: duration.as_nanos() as u64,
+        },
+        Err(error) => failed(error),
+    }
+}
+
+/// Releases the strings of answers returned by `advent_solve`.
+///
+/// # Safety
+///
+/// `answers` must come from `advent_solve` and not have been released yet.
+#[no_mangle]
+pub unsafe extern "C" fn advent_free_answers(answers: AdventAnswers) {
+    for s in [answers.part1, answers.part2, answers.error] {
+        if !s.is_null() {
+            drop(CString::from_raw(s));
+        }
+    }
+}
+
+#[test]
+fn check_header() {
+    let generated = concat!(env!("OUT_DIR"), "/advent_2022.h");
+
+    assert!(
+        include_str!(concat!(env!("OUT_DIR"), "/advent_2022.h"))
+            == include_str!("../advent_2022.h"),
+        "capi/advent_2022.h is out of date, copy {generated} over it"
+    );
+}
@@ -0,0 +1,39 @@
+//! Python module `advent_2022` exposing the solvers, built into a wheel by
+//! `maturin build --release` run next to this crate's manifest.
+
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use std::time::Duration;
+
+/// Numbers of the registered days.
+#[pyfunction]
+#[pyo3(name = "days")]
+fn registered_days() -> Vec<u32> {
+    advent_2022::days()
+}
+
+/// Resolves `day` on `input`, returns both answers and the time it took as a
+/// `timedelta`. Raises `ValueError` for a day which isn't registered and
+/// `RuntimeError` when the day fails on `input`.
+#[pyfunction]
+fn solve(py: Python<'_>, day: u32, input: String) -> PyResult<(String, String, Duration)> {
+    let registered = advent_2022::days().contains(&day);
+
+    // other Python threads run meanwhile
+    py.detach(|| advent_2022::solve(day, input))
+        .map_err(|error| {
+            if registered {
+                PyRuntimeError::new_err(error)
+            } else {
+                PyValueError::new_err(error)
+            }
+        })
+}
+
+#[pymodule]
+#[pyo3(name = "advent_2022")]
+fn advent_2022_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(registered_days, m)?)?;
+    m.add_function(wrap_pyfunction!(solve, m)?)?;
+    Ok(())
+}
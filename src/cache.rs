@@ -0,0 +1,117 @@
+use crate::{params, read_raw_input};
+use std::collections::hash_map::DefaultHasher;
+use std::env;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+
+const CACHE_DIR: &str = "./cache";
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static REFRESH: AtomicBool = AtomicBool::new(false);
+static BUILD_ID: OnceLock<u64> = OnceLock::new();
+
+/// Answers are read from the cache and stored into it from now on. With
+/// `refresh` they are only stored, every day being resolved again.
+pub fn enable(refresh: bool) {
+    ENABLED.store(true, Ordering::Relaxed);
+    REFRESH.store(refresh, Ordering::Relaxed);
+}
+
+fn hash<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+// any rebuild of the executable invalidates the answers, a changed solution
+// may well give different ones
+fn build_id() -> u64 {
+    *BUILD_ID.get_or_init(|| {
+        env::current_exe()
+            .and_then(fs::metadata)
+            .and_then(|m| Ok((m.len(), m.modified()?)))
+            .map(|id| hash(&id))
+            .unwrap_or_default()
+    })
+}
+
+// the overridden parameters change the answers as much as the input
+fn entry_path(day_number: u32, input: &[u8], params: &[(&str, String)], build_id: u64) -> String {
+    format!(
+        "{CACHE_DIR}/day{day_number:0>2}-{:016x}-{build_id:016x}.txt",
+        hash(&(input, params))
+    )
+}
+
+fn current_entry_path(day_number: u32) -> Option<String> {
+    let input = read_raw_input(day_number).ok()?;
+
+    Some(entry_path(
+        day_number,
+        input.as_bytes(),
+        &params::overridden(day_number),
+        build_id(),
+    ))
+}
+
+/// Answers stored by a previous run of the same binary on the same input.
+pub fn load(day_number: u32) -> Option<(String, String)> {
+    if !ENABLED.load(Ordering::Relaxed) || REFRESH.load(Ordering::Relaxed) {
+        return None;
+    }
+
+    let entry = fs::read_to_string(current_entry_path(day_number)?).ok()?;
+    let (part1, part2) = entry.split_once('\n')?;
+
+    Some((part1.to_string(), part2.to_string()))
+}
+
+pub fn store(day_number: u32, part1: &str, part2: &str) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+
+    let Some(path) = current_entry_path(day_number) else {
+        return;
+    };
+
+    // a failing cache only makes the next run slower
+    if let Err(e) =
+        fs::create_dir_all(CACHE_DIR).and_then(|_| fs::write(&path, format!("{part1}\n{part2}")))
+    {
+        eprintln!("cannot write {path}: {e}");
+    }
+}
+
+/// Removes every cached answer, returning how many there were.
+pub fn clear() -> io::Result<usize> {
+    let entries = match fs::read_dir(CACHE_DIR) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e),
+    };
+    let mut count = 0;
+
+    for entry in entries {
+        fs::remove_file(entry?.path())?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+#[test]
+fn check_entry_path() {
+    let disk = [("disk", "100".to_string())];
+    let path = entry_path(7, b"$ cd /\n", &[], 1);
+
+    assert!(path.starts_with("./cache/day07-"));
+    assert_eq!(path, entry_path(7, b"$ cd /\n", &[], 1));
+    assert_ne!(path, entry_path(7, b"$ cd a\n", &[], 1));
+    assert_ne!(path, entry_path(7, b"$ cd /\n", &disk, 1));
+    assert_ne!(path, entry_path(7, b"$ cd /\n", &[], 2));
+    assert_ne!(path, entry_path(8, b"$ cd /\n", &[], 1));
+}
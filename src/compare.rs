@@ -0,0 +1,56 @@
+use crate::{all_days, unavailable, Day};
+
+fn variants(day_number: u32) -> Vec<&'static Day> {
+    let mut variants: Vec<&'static Day> = inventory::iter::<Day>
+        .into_iter()
+        .filter(|d| d.parse_number() == day_number)
+        .collect();
+
+    variants.sort_unstable();
+    variants
+}
+
+fn compare_one(day_number: u32) {
+    let variants = variants(day_number);
+
+    if variants.is_empty() {
+        println!("{}", unavailable(day_number));
+        return;
+    }
+
+    let mut reference = None;
+    let results = variants
+        .iter()
+        .map(|v| {
+            let (_, part1, part2, duration) = v.timed_resolve();
+            let reference = *reference.get_or_insert(duration);
+
+            println!(
+                "day{day_number:0>2}: {:10} part1: {part1:20} part2: {part2:20} in {duration:?} ({:.2}x)",
+                v.name(),
+                duration.as_secs_f64() / reference.as_secs_f64()
+            );
+            (part1, part2)
+        })
+        .collect::<Vec<_>>();
+
+    if results.iter().any(|r| *r != results[0]) {
+        println!("day{day_number:0>2}: variants disagree");
+    }
+}
+
+/// Runs every registered implementation of `days` (all days having variants
+/// if empty) on the same input and checks that their answers agree.
+pub fn compare(days: &[u32]) {
+    let days = if days.is_empty() {
+        all_days()
+            .iter()
+            .map(|d| d.parse_number())
+            .filter(|&d| variants(d).len() > 1)
+            .collect()
+    } else {
+        days.to_vec()
+    };
+
+    days.iter().for_each(|&d| compare_one(d));
+}
@@ -0,0 +1,145 @@
+use crate::params::{self, Override};
+use crate::{trace, Output};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CONFIG_FILENAME: &str = "advent.toml";
+
+/// Defaults of the command line, read from the first `advent.toml` found in
+/// the current directory or its parents. Paths are relative to the file.
+/// `days` are resolved, watched, compared or scaled when the command line
+/// gives none, `--no-record` and `--no-trace` turn off what the file turns
+/// on.
+///
+/// ```toml
+/// inputs = "../private/inputs"
+/// nthreads = 4
+/// timeout = 60
+/// days = [15, 16]
+/// output = "json"
+/// trace_format = "folded"
+/// record = true
+///
+/// [params]
+/// rounds2 = 1000
+///
+/// [params.15]
+/// row = 10
+/// size = 20
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub inputs: Option<PathBuf>,
+    pub nthreads: Option<usize>,
+    /// seconds
+    pub timeout: Option<u64>,
+    #[serde(default)]
+    pub days: Vec<u32>,
+    pub output: Option<Output>,
+    pub trace: Option<PathBuf>,
+    pub trace_format: Option<trace::Format>,
+    /// append the timings to the history file
+    #[serde(default)]
+    pub record: bool,
+    // values for every day declaring them, or tables of the values of a day
+    #[serde(default)]
+    params: BTreeMap<String, toml::Value>,
+}
+
+impl Config {
+    fn parse(content: &str, dir: &Path) -> Result<Self, String> {
+        let mut config: Config = toml::from_str(content).map_err(|e| e.to_string())?;
+
+        config.inputs = config.inputs.map(|p| dir.join(p));
+        config.trace = config.trace.map(|p| dir.join(p));
+        Ok(config)
+    }
+
+    /// Loads the configuration file, the default configuration if there is
+    /// none.
+    pub fn discover() -> Result<Self, String> {
+        let cwd = env::current_dir().map_err(|e| e.to_string())?;
+        let Some(path) = cwd
+            .ancestors()
+            .map(|dir| dir.join(CONFIG_FILENAME))
+            .find(|path| path.is_file())
+        else {
+            return Ok(Config::default());
+        };
+        let content = fs::read_to_string(&path).map_err(|e| format!("{}: {e}", path.display()))?;
+
+        Config::parse(&content, path.parent().unwrap())
+            .map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    /// Parameters set by the file, as if given with `--param`.
+    pub fn overrides(&self) -> Result<Vec<Override>, String> {
+        fn to_string(value: &toml::Value) -> String {
+            match value {
+                toml::Value::String(s) => s.clone(),
+                value => value.to_string(),
+            }
+        }
+
+        let mut overrides = vec![];
+
+        for (key, value) in &self.params {
+            match value {
+                toml::Value::Table(day) => {
+                    for (name, value) in day {
+                        overrides.push(params::parse_override(&format!(
+                            "{key}.{name}={}",
+                            to_string(value)
+                        ))?);
+                    }
+                }
+                value => overrides.push(params::parse_override(&format!(
+                    "{key}={}",
+                    to_string(value)
+                ))?),
+            }
+        }
+        Ok(overrides)
+    }
+}
+
+#[test]
+fn check_parse() {
+    let config = Config::parse(
+        r#"
+inputs = "inputs"
+nthreads = 2
+days = [1, 2]
+output = "json"
+trace_format = "folded"
+record = true
+"#,
+        Path::new("/advent"),
+    )
+    .unwrap();
+
+    assert_eq!(config.inputs, Some(PathBuf::from("/advent/inputs")));
+    assert_eq!(config.nthreads, Some(2));
+    assert_eq!(config.days, vec![1, 2]);
+    assert_eq!(config.output, Some(Output::Json));
+    assert_eq!(config.trace_format, Some(trace::Format::Folded));
+    assert!(config.record);
+    assert!(config.overrides().unwrap().is_empty());
+    assert!(Config::parse("nthread = 2", Path::new("/")).is_err());
+    // the parameter registered by the tests of params
+    assert_eq!(
+        Config::parse("[params.99]\ncheck = 2", Path::new("/"))
+            .unwrap()
+            .overrides()
+            .map(|o| o.len()),
+        Ok(1)
+    );
+    assert!(Config::parse("[params]\nunknown = 10", Path::new("/"))
+        .unwrap()
+        .overrides()
+        .is_err());
+}
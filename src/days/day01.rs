@@ -1,19 +1,15 @@
-use std::io::{BufRead, Lines};
-
-fn resolve<T>(lines: Lines<T>) -> (u32, u32)
-where
-    T: BufRead,
-{
+fn resolve(input: &str) -> (u32, u32) {
     let mut elves = vec![];
     let mut elve_calories = 0;
 
-    for line in lines {
-        let line = line.unwrap();
-        if let Ok(calories) = line.parse::<u32>() {
-            elve_calories += calories;
-        } else {
+    for line in input.lines() {
+        let line = line.trim();
+
+        if line.is_empty() {
             elves.push(elve_calories);
             elve_calories = 0;
+        } else {
+            elve_calories += line.parse::<u32>().unwrap();
         }
     }
     elves.push(elve_calories);
@@ -39,20 +35,19 @@ fn check() {
 9000
 
 10000";
-    use std::io::Cursor;
 
-    let (part1, part2) = resolve(Cursor::new(TEST).lines());
+    let (part1, part2) = resolve(TEST);
 
     assert_eq!(part1, 24000);
     assert_eq!(part2, 45000);
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
+fn resolve_string(input: &str) -> (String, String) {
+    let solution = resolve(input);
     (solution.0.to_string(), solution.1.to_string())
 }
 
-inventory::submit! { advent_2022::Day::new(file!(), resolve_string) }
+inventory::submit! {
+    advent_2022::Day::new(file!(), resolve_string)
+        .title("Calorie Counting")
+}
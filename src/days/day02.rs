@@ -1,5 +1,3 @@
-use std::io::{BufRead, Lines};
-
 #[derive(PartialEq)]
 enum Shape {
     Rock,
@@ -85,14 +83,10 @@ fn round_score_part2(elve: &Shape, strategy: &str) -> u32 {
 }
 
 // First try
-fn _resolve<T>(lines: Lines<T>) -> (u32, u32)
-where
-    T: BufRead,
-{
+fn resolve_loop(input: &str) -> (u32, u32) {
     let mut scores = (0, 0);
 
-    for line in lines {
-        let line = line.unwrap();
+    for line in input.lines() {
         let words = line.split(' ').collect::<Vec<_>>();
         let elve = match words[0] {
             "A" => Shape::Rock,
@@ -108,12 +102,9 @@ where
 }
 
 // Using fold
-fn resolve<T>(lines: Lines<T>) -> (u32, u32)
-where
-    T: BufRead,
-{
-    lines.fold((0, 0), |scores, line| {
-        let words = line.as_ref().unwrap().split(' ').collect::<Vec<_>>();
+fn resolve(input: &str) -> (u32, u32) {
+    input.lines().fold((0, 0), |scores, line| {
+        let words = line.split(' ').collect::<Vec<_>>();
         let elve = match words[0] {
             "A" => Shape::Rock,
             "B" => Shape::Paper,
@@ -133,20 +124,27 @@ fn check() {
     const TEST: &str = "A Y
 B X
 C Z";
-    use std::io::Cursor;
 
-    let (part1, part2) = resolve(Cursor::new(TEST).lines());
+    let (part1, part2) = resolve(TEST);
+
+    assert_eq!(resolve_loop(TEST), (part1, part2));
 
     assert_eq!(part1, 15);
     assert_eq!(part2, 12);
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
+fn resolve_string(input: &str) -> (String, String) {
+    let solution = resolve(input);
     (solution.0.to_string(), solution.1.to_string())
 }
 
-inventory::submit! { advent_2022::Day::new(file!(), resolve_string) }
+fn resolve_loop_string(input: &str) -> (String, String) {
+    let solution = resolve_loop(input);
+    (solution.0.to_string(), solution.1.to_string())
+}
+
+inventory::submit! {
+    advent_2022::Day::new(file!(), resolve_string)
+        .title("Rock Paper Scissors")
+}
+inventory::submit! { advent_2022::Day::variant(file!(), "loop", resolve_loop_string) }
@@ -1,5 +1,3 @@
-use std::io::{BufRead, Lines};
-
 fn find_element(str1: &str, str2: &str) -> Option<char> {
     str1.chars().find(|c| str2.contains(*c))
 }
@@ -54,15 +52,11 @@ fn check_common() {
 }
 
 // more intuitive IMHO
-fn _resolve<T>(lines: Lines<T>) -> (i32, i32)
-where
-    T: BufRead,
-{
+fn resolve_loop(input: &str) -> (i32, i32) {
     let (mut part1, mut part2) = (0i32, 0i32);
     let mut group = vec![];
 
-    for line in lines {
-        let s = line.unwrap();
+    for s in input.lines() {
         let half = s.len() / 2;
 
         part1 += element_priority(find_element(&s[0..half], &s[half..]).unwrap());
@@ -70,7 +64,7 @@ where
         group.push(s);
 
         if group.len() == 3 {
-            part2 += element_priority(common_element(&group[0], &group[1], &group[2]));
+            part2 += element_priority(common_element(group[0], group[1], group[2]));
             group.clear();
         }
     }
@@ -79,14 +73,10 @@ where
 }
 
 // Using fold
-fn resolve<T>(lines: Lines<T>) -> (i32, i32)
-where
-    T: BufRead,
-{
+fn resolve(input: &str) -> (i32, i32) {
     let mut group = vec![];
 
-    lines.fold((0, 0), |scores, line| {
-        let line = line.unwrap();
+    input.lines().fold((0, 0), |scores, line| {
         let half = line.len() / 2;
 
         let part1 = element_priority(find_element(&line[0..half], &line[half..]).unwrap());
@@ -95,7 +85,7 @@ where
         group.push(line);
 
         if group.len() == 3 {
-            part2 = element_priority(common_element(&group[0], &group[1], &group[2]));
+            part2 = element_priority(common_element(group[0], group[1], group[2]));
             group.clear();
         } else {
             part2 = 0;
@@ -112,20 +102,27 @@ PmmdzqPrVvPwwTWBwg
 wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
 ttgJtRGJQctTZtZT
 CrZsJsPPZsGzwwsLwLmpwMDw";
-    use std::io::Cursor;
 
-    let (part1, part2) = resolve(Cursor::new(TEST).lines());
+    let (part1, part2) = resolve(TEST);
+
+    assert_eq!(resolve_loop(TEST), (part1, part2));
 
     assert_eq!(part1, 157);
     assert_eq!(part2, 70);
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
+fn resolve_string(input: &str) -> (String, String) {
+    let solution = resolve(input);
+    (solution.0.to_string(), solution.1.to_string())
+}
+
+fn resolve_loop_string(input: &str) -> (String, String) {
+    let solution = resolve_loop(input);
     (solution.0.to_string(), solution.1.to_string())
 }
 
-inventory::submit! { advent_2022::Day::new(file!(), resolve_string) }
+inventory::submit! {
+    advent_2022::Day::new(file!(), resolve_string)
+        .title("Rucksack Reorganization")
+}
+inventory::submit! { advent_2022::Day::variant(file!(), "loop", resolve_loop_string) }
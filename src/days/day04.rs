@@ -1,17 +1,15 @@
-use std::io::{BufRead, Lines};
-
 fn range_contains(a: (u32, u32), b: (u32, u32)) -> bool {
     !((a.0 > b.0 || a.1 < b.1) && (b.0 > a.0 || b.1 < a.1))
 }
 
 #[test]
 fn check_contains() {
-    assert_eq!(range_contains((2, 4), (6, 8)), false);
-    assert_eq!(range_contains((2, 3), (4, 5)), false);
-    assert_eq!(range_contains((5, 7), (7, 9)), false);
-    assert_eq!(range_contains((2, 8), (3, 7)), true);
-    assert_eq!(range_contains((6, 6), (4, 6)), true);
-    assert_eq!(range_contains((2, 6), (4, 8)), false);
+    assert!(!range_contains((2, 4), (6, 8)));
+    assert!(!range_contains((2, 3), (4, 5)));
+    assert!(!range_contains((5, 7), (7, 9)));
+    assert!(range_contains((2, 8), (3, 7)));
+    assert!(range_contains((6, 6), (4, 6)));
+    assert!(!range_contains((2, 6), (4, 8)));
 }
 
 fn range_overlap(a: (u32, u32), b: (u32, u32)) -> bool {
@@ -20,21 +18,17 @@ fn range_overlap(a: (u32, u32), b: (u32, u32)) -> bool {
 
 #[test]
 fn check_overlap() {
-    assert_eq!(range_overlap((2, 4), (6, 8)), false);
-    assert_eq!(range_overlap((2, 3), (4, 5)), false);
-    assert_eq!(range_overlap((5, 7), (7, 9)), true);
-    assert_eq!(range_overlap((2, 8), (3, 7)), true);
-    assert_eq!(range_overlap((6, 6), (4, 6)), true);
-    assert_eq!(range_overlap((2, 6), (4, 8)), true);
+    assert!(!range_overlap((2, 4), (6, 8)));
+    assert!(!range_overlap((2, 3), (4, 5)));
+    assert!(range_overlap((5, 7), (7, 9)));
+    assert!(range_overlap((2, 8), (3, 7)));
+    assert!(range_overlap((6, 6), (4, 6)));
+    assert!(range_overlap((2, 6), (4, 8)));
 }
 
-fn resolve<T>(lines: Lines<T>) -> (u32, u32)
-where
-    T: BufRead,
-{
-    lines.fold((0, 0), |scores, line| {
+fn resolve(input: &str) -> (u32, u32) {
+    input.lines().fold((0, 0), |scores, line| {
         let values: Vec<u32> = line
-            .unwrap()
             .split(|c: char| !c.is_ascii_digit())
             .map(|s| s.parse::<u32>().unwrap())
             .collect();
@@ -55,20 +49,19 @@ fn check() {
 2-8,3-7
 6-6,4-6
 2-6,4-8";
-    use std::io::Cursor;
 
-    let (part1, part2) = resolve(Cursor::new(TEST).lines());
+    let (part1, part2) = resolve(TEST);
 
     assert_eq!(part1, 2);
     assert_eq!(part2, 4);
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
+fn resolve_string(input: &str) -> (String, String) {
+    let solution = resolve(input);
     (solution.0.to_string(), solution.1.to_string())
 }
 
-inventory::submit! { advent_2022::Day::new(file!(), resolve_string) }
+inventory::submit! {
+    advent_2022::Day::new(file!(), resolve_string)
+        .title("Camp Cleanup")
+}
@@ -1,5 +1,5 @@
+use rand::{rngs::StdRng, Rng};
 use regex::Regex;
-use std::io::{BufRead, Lines};
 
 #[derive(Clone)]
 struct Crate {
@@ -16,24 +16,21 @@ impl Crate {
     }
 }
 
-fn resolve<T>(mut lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
+fn resolve(input: &str) -> (String, String) {
     let move_regex = Regex::new(r"^move (\d+) from (\d+) to (\d+)$").unwrap();
     let mut crates: Vec<Crate> = vec![];
+    let mut lines = input.lines();
 
     for line in lines.by_ref() {
-        let line = line.unwrap();
-
-        if line.is_empty() {
+        if line.trim().is_empty() {
             break;
         }
 
+        // the padding of the lines may have been trimmed
         let len = (line.len() + 1) / 4;
 
-        if crates.is_empty() {
-            crates = vec![Crate::new(); len];
+        if crates.len() < len {
+            crates.resize(len, Crate::new());
         }
 
         for i in 0..len {
@@ -47,8 +44,7 @@ where
     }
 
     for line in lines {
-        let line = line.unwrap();
-        let move_capture = move_regex.captures(&line).unwrap();
+        let move_capture = move_regex.captures(line).unwrap();
         let count = move_capture
             .get(1)
             .unwrap()
@@ -99,12 +95,82 @@ move 1 from 2 to 1
 move 3 from 1 to 3
 move 2 from 2 to 1
 move 1 from 1 to 2";
-    use std::io::Cursor;
 
-    let (part1, part2) = resolve(Cursor::new(TEST).lines());
+    let (part1, part2) = resolve(TEST);
 
     assert_eq!(part1, "CMZ");
     assert_eq!(part2, "MCD");
+
+    let trimmed = TEST
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    assert_eq!(resolve(&trimmed), (part1, part2));
+}
+
+// `size` moves, a stack is never emptied so both parts always have an answer
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let count = rng.gen_range(3..10);
+    let mut stacks = (0..count)
+        .map(|_| {
+            (0..rng.gen_range(2..20))
+                .map(|_| rng.gen_range(b'A'..=b'Z') as char)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let height = stacks.iter().map(|s| s.len()).max().unwrap();
+    let mut input = String::new();
+
+    for level in (0..height).rev() {
+        let row = stacks
+            .iter()
+            .map(|s| s.get(level).map_or("   ".to_string(), |c| format!("[{c}]")))
+            .collect::<Vec<_>>();
+
+        input.push_str(&row.join(" "));
+        input.push('\n');
+    }
+
+    let numbers = (1..count + 1).map(|i| format!(" {i} ")).collect::<Vec<_>>();
+
+    input.push_str(&numbers.join(" "));
+    input.push_str("\n\n");
+
+    for _ in 0..size {
+        let from = loop {
+            let from = rng.gen_range(0..count);
+
+            if stacks[from].len() > 1 {
+                break from;
+            }
+        };
+        let to = (from + rng.gen_range(1..count)) % count;
+        let moved = rng.gen_range(1..stacks[from].len());
+        let at = stacks[from].len() - moved;
+        let crates = stacks[from].split_off(at);
+
+        stacks[to].extend(crates);
+        input.push_str(&format!("move {moved} from {} to {}\n", from + 1, to + 1));
+    }
+
+    input
+}
+
+#[test]
+fn check_generate() {
+    use rand::SeedableRng;
+
+    let input = generate(&mut StdRng::seed_from_u64(5), 1000);
+    let (part1, part2) = resolve(&input);
+
+    assert_eq!(part1.len(), part2.len());
 }
 
-inventory::submit! { advent_2022::Day::new(file!(), resolve) }
+inventory::submit! {
+    advent_2022::Day::new(file!(), resolve)
+        .title("Supply Stacks")
+        .padded()
+}
+inventory::submit! { advent_2022::generate::Generator::new(file!(), generate, 1000) }
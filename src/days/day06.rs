@@ -1,5 +1,3 @@
-use std::io::{BufRead, Lines};
-
 fn get_index(buf_slice: &[u8]) -> Option<usize> {
     buf_slice.iter().enumerate().rev().find_map(|(i, c1)| {
         buf_slice
@@ -14,7 +12,7 @@ fn get_index(buf_slice: &[u8]) -> Option<usize> {
 fn find_first_index(buffer: &[u8], distincts: usize) -> usize {
     let mut index = 0;
 
-    while index < buffer.len() - distincts {
+    while index + distincts <= buffer.len() {
         let idx = get_index(&buffer[index..index + distincts]);
 
         if let Some(idx) = idx {
@@ -26,13 +24,37 @@ fn find_first_index(buffer: &[u8], distincts: usize) -> usize {
     panic!("");
 }
 
-fn resolve<T>(lines: Lines<T>) -> Vec<(usize, usize)>
-where
-    T: BufRead,
-{
-    lines
+#[test]
+fn check_random() {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use std::collections::HashSet;
+
+    let mut rng = StdRng::seed_from_u64(6);
+
+    for _ in 0..1000 {
+        let distincts = rng.gen_range(2..15);
+        let letters = rng.gen_range(distincts..27) as u8;
+        let buffer = (0..rng.gen_range(distincts..100))
+            .map(|_| b'a' + rng.gen_range(0..letters))
+            .collect::<Vec<_>>();
+        let expected = (distincts..buffer.len() + 1).find(|&i| {
+            buffer[i - distincts..i]
+                .iter()
+                .collect::<HashSet<_>>()
+                .len()
+                == distincts
+        });
+
+        if let Some(expected) = expected {
+            assert_eq!(find_first_index(&buffer, distincts), expected);
+        }
+    }
+}
+
+fn resolve(input: &str) -> Vec<(usize, usize)> {
+    input
+        .lines()
         .map(|line| {
-            let line = line.unwrap();
             let buf = line.as_bytes();
 
             (find_first_index(buf, 4), find_first_index(buf, 14))
@@ -47,19 +69,18 @@ bvwbjplbgvbhsrlpgdmjqwftvncz
 nppdvjthqldpwncqszvftbrmjlhg
 nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg
 zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw";
-    use std::io::Cursor;
 
-    let parts = resolve(Cursor::new(TEST).lines());
+    let parts = resolve(TEST);
 
     assert_eq!(parts, [(7, 19), (5, 23), (6, 23), (10, 29), (11, 26)])
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
+fn resolve_string(input: &str) -> (String, String) {
+    let solution = resolve(input);
     (solution[0].0.to_string(), solution[0].1.to_string())
 }
 
-inventory::submit! { advent_2022::Day::new(file!(), resolve_string) }
+inventory::submit! {
+    advent_2022::Day::new(file!(), resolve_string)
+        .title("Tuning Trouble")
+}
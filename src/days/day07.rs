@@ -1,5 +1,6 @@
+use advent_2022::params;
+use rand::{rngs::StdRng, Rng};
 use regex::Regex;
-use std::io::{BufRead, Lines};
 
 fn pop_and_add(stack: &mut Vec<usize>) -> usize {
     let current = stack.pop().unwrap();
@@ -11,18 +12,14 @@ fn pop_and_add(stack: &mut Vec<usize>) -> usize {
     current
 }
 
-fn resolve<T>(lines: Lines<T>) -> (usize, usize)
-where
-    T: BufRead,
-{
+fn resolve(input: &str) -> (usize, usize) {
     let command_regexp = Regex::new(r"^\$ (ls|cd \S+)$").unwrap();
     let size_regexp = Regex::new(r"^(\S+) \S+$").unwrap();
     let mut stack = vec![];
     let mut dirs = vec![];
 
-    for line in lines {
-        let line = line.unwrap();
-        let command_capture = command_regexp.captures(&line);
+    for line in input.lines() {
+        let command_capture = command_regexp.captures(line);
 
         if let Some(command_capture) = command_capture {
             let command = command_capture.get(1).unwrap().as_str();
@@ -33,7 +30,7 @@ where
                 _ => stack.push(0),
             }
         } else {
-            let size_capture = size_regexp.captures(&line).unwrap();
+            let size_capture = size_regexp.captures(line).unwrap();
             let size = size_capture.get(1).unwrap().as_str().parse::<usize>();
 
             if let Ok(size) = size {
@@ -50,12 +47,16 @@ where
 
     dirs.sort();
 
-    let needed = dirs.last().unwrap() - 40000000;
+    let disk = params::get::<usize>(file!(), "disk");
+    let unused = params::get::<usize>(file!(), "unused");
+    // any directory frees enough when there is already enough unused space
+    let needed = (dirs.last().unwrap() + unused).saturating_sub(disk);
+    let deleted = dirs
+        .iter()
+        .find(|x| x > &&needed)
+        .unwrap_or_else(|| panic!("no directory frees {needed} for {unused} unused on a disk of {disk}"));
 
-    (
-        dirs.iter().filter(|x| x < &&100000).sum(),
-        *dirs.iter().find(|x| x > &&needed).unwrap(),
-    )
+    (dirs.iter().filter(|x| x < &&100000).sum(), *deleted)
 }
 
 #[test]
@@ -83,20 +84,127 @@ $ ls
 8033020 d.log
 5626152 d.ext
 7214296 k";
-    use std::io::Cursor;
 
-    let (part1, part2) = resolve(Cursor::new(TEST).lines());
+    let (part1, part2) = resolve(TEST);
 
     assert_eq!(part1, 95437);
     assert_eq!(part2, 24933642);
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
+fn resolve_string(input: &str) -> (String, String) {
+    let solution = resolve(input);
     (solution.0.to_string(), solution.1.to_string())
 }
 
-inventory::submit! { advent_2022::Day::new(file!(), resolve_string) }
+fn random_name(rng: &mut StdRng) -> String {
+    (0..rng.gen_range(1..9))
+        .map(|_| rng.gen_range('a'..='z'))
+        .collect()
+}
+
+struct Directory {
+    name: String,
+    files: Vec<(usize, String)>,
+    children: Vec<usize>,
+}
+
+fn transcript(tree: &[Directory], index: usize, input: &mut String) {
+    let directory = &tree[index];
+
+    input.push_str(&format!("$ cd {}\n$ ls\n", directory.name));
+
+    for &c in &directory.children {
+        input.push_str(&format!("dir {}\n", tree[c].name));
+    }
+    for (size, name) in &directory.files {
+        input.push_str(&format!("{size} {name}\n"));
+    }
+    for &c in &directory.children {
+        transcript(tree, c, input);
+        input.push_str("$ cd ..\n");
+    }
+}
+
+// `size` directories on a disk used above the 40000000 the update needs to be
+// installed
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let mut tree = vec![];
+    let mut total = 0;
+
+    for i in 0..size + 1 {
+        let files = (0..rng.gen_range(0..6))
+            .map(|_| {
+                let file_size = rng.gen_range(1..300000);
+
+                total += file_size;
+                (
+                    file_size,
+                    format!("{}.{}", random_name(rng), random_name(rng)),
+                )
+            })
+            .collect();
+        let name = if i == 0 {
+            String::from("/")
+        } else {
+            // unique among all directories so that siblings never clash
+            format!("{}{i}", random_name(rng))
+        };
+
+        tree.push(Directory {
+            name,
+            files,
+            children: vec![],
+        });
+
+        if i > 0 {
+            let parent = rng.gen_range(0..i);
+
+            tree[parent].children.push(i);
+        }
+    }
+
+    if total == 0 {
+        tree[0].files.push((1, random_name(rng)));
+        total = 1;
+    }
+
+    // scaled to fill the disk of 70000000 above the 40000000 the update needs
+    // free, some directory other than / is then deleted
+    let used = rng.gen_range(40100000..69900000);
+
+    for directory in &mut tree {
+        for (file_size, _) in &mut directory.files {
+            *file_size = usize::max(1, *file_size * used / total);
+        }
+    }
+
+    let mut input = String::new();
+
+    transcript(&tree, 0, &mut input);
+    input
+}
+
+#[test]
+fn check_generate() {
+    use rand::SeedableRng;
+
+    let input = generate(&mut StdRng::seed_from_u64(7), 1000);
+    let used = input
+        .lines()
+        .filter_map(|line| line.split_once(' ')?.0.parse::<usize>().ok())
+        .sum::<usize>();
+    let (part1, part2) = resolve(&input);
+
+    assert!((40000001..=70000000).contains(&used), "{used} used");
+    assert!(part1 > 0);
+    // not /
+    assert!(part2 < used);
+}
+
+inventory::submit! {
+    advent_2022::Day::new(file!(), resolve_string)
+        .title("No Space Left On Device")
+}
+inventory::submit! { advent_2022::generate::Generator::new(file!(), generate, 1000) }
+inventory::submit! { advent_2022::params::Param::new(file!(), "disk", "70000000", "total size of the filesystem") }
+inventory::submit! { advent_2022::params::Param::new(file!(), "unused", "30000000", "unused space needed by the update") }
@@ -1,5 +1,3 @@
-use std::io::{BufRead, Lines};
-
 fn get_max(heights: &[Option<usize>; 10]) -> u8 {
     for (i, height) in heights.iter().enumerate().rev() {
         if height.is_some() {
@@ -69,7 +67,7 @@ impl Tree {
     }
 }
 
-fn compute_max_and_visible(grid: &mut Vec<Vec<Tree>>) {
+fn compute_max_and_visible(grid: &mut [Vec<Tree>]) {
     for row in grid.iter_mut() {
         let mut last_indices_left = [None; 10];
         let mut last_indices_right = [None; 10];
@@ -111,14 +109,10 @@ fn compute_max_and_visible(grid: &mut Vec<Vec<Tree>>) {
     }
 }
 
-fn resolve<T>(lines: Lines<T>) -> (usize, usize)
-where
-    T: BufRead,
-{
+fn resolve(input: &str) -> (usize, usize) {
     let mut grid = vec![];
 
-    for line in lines {
-        let line = line.unwrap();
+    for line in input.lines() {
         let array: Vec<Tree> = line
             .as_bytes()
             .iter()
@@ -159,20 +153,19 @@ fn check() {
 65332
 33549
 35390";
-    use std::io::Cursor;
 
-    let (part1, part2) = resolve(Cursor::new(TEST).lines());
+    let (part1, part2) = resolve(TEST);
 
     assert_eq!(part1, 21);
     assert_eq!(part2, 8);
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
+fn resolve_string(input: &str) -> (String, String) {
+    let solution = resolve(input);
     (solution.0.to_string(), solution.1.to_string())
 }
 
-inventory::submit! { advent_2022::Day::new(file!(), resolve_string) }
+inventory::submit! {
+    advent_2022::Day::new(file!(), resolve_string)
+        .title("Treetop Tree House")
+}
@@ -1,5 +1,6 @@
+use advent_2022::params;
+use advent_2022::simulation::{Simulation, Simulator};
 use std::collections::HashSet;
-use std::io::{BufRead, Lines};
 
 #[derive(Debug, Clone)]
 struct Point {
@@ -34,45 +35,133 @@ impl Point {
     }
 }
 
-fn resolve<T>(lines: Lines<T>) -> (usize, usize)
-where
-    T: BufRead,
-{
-    let size = 9;
-    let mut head = Point::new();
-    let mut knots = vec![Point::new(); size];
-    let mut part1 = HashSet::new();
-    let mut part2 = HashSet::new();
-
-    for line in lines {
-        let line = line.unwrap();
-        let mut split = line.split(' ');
-        let direction = split.next().unwrap();
-        let value = split.next().unwrap().parse::<usize>().unwrap();
-
-        let vector = match direction {
-            "L" => (-1, 0),
-            "R" => (1, 0),
-            "U" => (0, 1),
-            "D" => (0, -1),
-            _ => panic!(),
-        };
-
-        for _ in 0..value {
-            head = head.add(vector);
-
-            knots[0] = knots[0].follow(&head);
-
-            for i in 1..size {
-                knots[i] = knots[i].follow(&knots[i - 1]);
+struct Rope {
+    motions: Vec<((i32, i32), usize)>,
+    motion: usize,
+    remaining: usize,
+    head: Point,
+    knots: Vec<Point>,
+    part1: HashSet<(i32, i32)>,
+    part2: HashSet<(i32, i32)>,
+}
+
+impl Rope {
+    fn new(input: &str, size: usize) -> Self {
+        let motions = input
+            .lines()
+            .map(|line| {
+                let mut split = line.split(' ');
+                let direction = split.next().unwrap();
+                let value = split.next().unwrap().parse::<usize>().unwrap();
+
+                let vector = match direction {
+                    "L" => (-1, 0),
+                    "R" => (1, 0),
+                    "U" => (0, 1),
+                    "D" => (0, -1),
+                    _ => panic!(),
+                };
+
+                (vector, value)
+            })
+            .collect::<Vec<_>>();
+        let remaining = motions.first().map_or(0, |m| m.1);
+
+        Rope {
+            motions,
+            motion: 0,
+            remaining,
+            head: Point::new(),
+            knots: vec![Point::new(); size],
+            part1: HashSet::new(),
+            part2: HashSet::new(),
+        }
+    }
+}
+
+impl Simulation for Rope {
+    fn step(&mut self) -> bool {
+        while self.remaining == 0 {
+            self.motion += 1;
+
+            if self.motion >= self.motions.len() {
+                return false;
             }
+            self.remaining = self.motions[self.motion].1;
+        }
+
+        let size = self.knots.len();
+
+        self.head = self.head.add(self.motions[self.motion].0);
+        self.remaining -= 1;
+
+        self.knots[0] = self.knots[0].follow(&self.head);
+
+        for i in 1..size {
+            self.knots[i] = self.knots[i].follow(&self.knots[i - 1]);
+        }
+
+        self.part1.insert((self.knots[0].x, self.knots[0].y));
+        self.part2
+            .insert((self.knots[size - 1].x, self.knots[size - 1].y));
 
-            part1.insert((knots[0].x, knots[0].y));
-            part2.insert((knots[size - 1].x, knots[size - 1].y));
+        true
+    }
+
+    fn render(&self) -> String {
+        let head = (self.head.x, self.head.y);
+        let knots = self.knots.iter().map(|k| (k.x, k.y)).collect::<Vec<_>>();
+        let mut points = vec![head];
+
+        points.extend(&knots);
+        points.extend(&self.part1);
+        points.extend(&self.part2);
+
+        let minx = points.iter().map(|p| p.0).min().unwrap();
+        let maxx = points.iter().map(|p| p.0).max().unwrap();
+        let miny = points.iter().map(|p| p.1).min().unwrap();
+        let maxy = points.iter().map(|p| p.1).max().unwrap();
+        let mut s = String::from("");
+
+        for y in (miny..maxy + 1).rev() {
+            for x in minx..maxx + 1 {
+                if head == (x, y) {
+                    s.push('H');
+                } else if let Some(i) = knots.iter().position(|&k| k == (x, y)) {
+                    s.push(char::from_digit(i as u32 + 1, 10).unwrap_or('T'));
+                } else if self.part2.contains(&(x, y)) {
+                    s.push('#');
+                } else if self.part1.contains(&(x, y)) {
+                    s.push('+');
+                } else {
+                    s.push('.');
+                }
+            }
+            s.push('\n');
         }
+        s
     }
 
-    (part1.len(), part2.len())
+    fn counters(&self) -> Vec<(&'static str, i64)> {
+        vec![
+            ("motion", self.motion as i64),
+            ("part1", self.part1.len() as i64),
+            ("part2", self.part2.len() as i64),
+        ]
+    }
+}
+
+// head included
+fn knots() -> usize {
+    params::get(file!(), "knots")
+}
+
+fn resolve(input: &str) -> (usize, usize) {
+    let mut rope = Rope::new(input, knots() - 1);
+
+    while rope.step() {}
+
+    (rope.part1.len(), rope.part2.len())
 }
 
 #[test]
@@ -85,20 +174,26 @@ R 4
 D 1
 L 5
 R 2";
-    use std::io::Cursor;
 
-    let (part1, part2) = resolve(Cursor::new(TEST).lines());
+    let (part1, part2) = resolve(TEST);
 
     assert_eq!(part1, 13);
     assert_eq!(part2, 1);
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
+fn resolve_string(input: &str) -> (String, String) {
+    let solution = resolve(input);
     (solution.0.to_string(), solution.1.to_string())
 }
 
-inventory::submit! { advent_2022::Day::new(file!(), resolve_string) }
+inventory::submit! {
+    advent_2022::Day::new(file!(), resolve_string)
+        .title("Rope Bridge")
+}
+
+fn simulate(input: &str) -> Box<dyn Simulation> {
+    Box::new(Rope::new(input, knots() - 1))
+}
+
+inventory::submit! { Simulator::new(file!(), simulate) }
+inventory::submit! { advent_2022::params::Param::new(file!(), "knots", "10", "number of knots of the rope of part2").at_least(2) }
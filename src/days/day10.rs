@@ -1,4 +1,4 @@
-use std::io::{BufRead, Lines};
+use advent_2022::simulation::{Simulation, Simulator};
 
 struct Cpu {
     x: i32,
@@ -17,7 +17,7 @@ impl Cpu {
     fn tick(&mut self) {
         self.cycle += 1;
 
-        if (self.cycle + 20) % 40 == 0 {
+        if (self.cycle + 20).is_multiple_of(40) {
             self.signal_strength += self.x * self.cycle as i32;
         }
     }
@@ -56,30 +56,77 @@ impl Crt {
     }
 }
 
-fn resolve<T>(lines: Lines<T>) -> (i32, String)
-where
-    T: BufRead,
-{
-    let mut cpu = Cpu::new();
-    let mut crt = Crt::new();
+struct Device {
+    program: Vec<Option<i32>>,
+    pc: usize,
+    adding: bool,
+    cpu: Cpu,
+    crt: Crt,
+}
 
-    for line in lines {
-        let line = line.unwrap();
-        let value = line.split(' ').nth(1);
+impl Device {
+    fn new(input: &str) -> Self {
+        let program = input
+            .lines()
+            .map(|line| line.split(' ').nth(1).map(|v| v.parse::<i32>().unwrap()))
+            .collect();
 
-        cpu.tick();
-        crt.tick(cpu.x);
+        Device {
+            program,
+            pc: 0,
+            adding: false,
+            cpu: Cpu::new(),
+            crt: Crt::new(),
+        }
+    }
+}
 
-        if let Some(value) = value {
-            // addx
-            cpu.tick();
-            crt.tick(cpu.x);
+impl Simulation for Device {
+    fn step(&mut self) -> bool {
+        if self.pc >= self.program.len() {
+            return false;
+        }
 
-            cpu.addx(value.parse::<i32>().unwrap());
+        self.cpu.tick();
+        self.crt.tick(self.cpu.x);
+
+        match self.program[self.pc] {
+            // first cycle of addx
+            Some(_) if !self.adding => self.adding = true,
+            Some(value) => {
+                self.cpu.addx(value);
+                self.adding = false;
+                self.pc += 1;
+            }
+            None => self.pc += 1,
         }
+        true
+    }
+
+    fn render(&self) -> String {
+        let sprite = (0..40)
+            .map(|i| if (i - self.cpu.x).abs() < 2 { '#' } else { '.' })
+            .collect::<String>();
+
+        format!("{}\nsprite:\n{sprite}", self.crt.screen)
     }
 
-    (cpu.signal_strength, crt.screen)
+    fn counters(&self) -> Vec<(&'static str, i64)> {
+        vec![
+            ("cycle", self.cpu.cycle as i64),
+            ("x", self.cpu.x as i64),
+            ("signal", self.cpu.signal_strength as i64),
+            ("pc", self.pc as i64),
+        ]
+    }
+}
+
+fn resolve(input: &str) -> (i32, String) {
+    let mut device = Device::new(input);
+
+    while device.step() {}
+
+    (device.cpu.signal_strength, device.crt.screen)
 }
 
 #[test]
@@ -238,20 +285,25 @@ noop";
 ######......######......######......####
 #######.......#######.......#######.....
 ";
-    use std::io::Cursor;
 
-    let (part1, part2) = resolve(Cursor::new(TEST).lines());
+    let (part1, part2) = resolve(TEST);
 
     assert_eq!(part1, 13140);
     assert_eq!(part2, RESULT);
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
+fn resolve_string(input: &str) -> (String, String) {
+    let solution = resolve(input);
     (solution.0.to_string(), solution.1)
 }
 
-inventory::submit! { advent_2022::Day::new(file!(), resolve_string) }
+inventory::submit! {
+    advent_2022::Day::new(file!(), resolve_string)
+        .title("Cathode-Ray Tube")
+}
+
+fn simulate(input: &str) -> Box<dyn Simulation> {
+    Box::new(Device::new(input))
+}
+
+inventory::submit! { Simulator::new(file!(), simulate) }
@@ -1,5 +1,7 @@
+use advent_2022::progress::Progress;
+use advent_2022::{params, trace};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
 use regex::Regex;
-use std::io::{BufRead, Lines};
 
 #[derive(Debug, Clone)]
 enum Operation {
@@ -27,7 +29,7 @@ struct Monkey {
 }
 
 fn divise(item: u64, divisible: &(u64, usize, usize)) -> usize {
-    if item % divisible.0 == 0 {
+    if item.is_multiple_of(divisible.0) {
         divisible.1
     } else {
         divisible.2
@@ -63,39 +65,30 @@ impl Monkey {
     }
 }
 
-fn get_next_number<T, U>(iter: &mut std::io::Lines<T>) -> U
+fn get_next_number<U>(iter: &mut std::str::Lines) -> U
 where
-    T: BufRead,
     U: std::str::FromStr,
     <U as std::str::FromStr>::Err: std::fmt::Debug,
 {
     let number_regex = Regex::new(r"\d+").unwrap();
-    let n = iter.next().unwrap().unwrap();
-    number_regex
-        .find(&n)
-        .unwrap()
-        .as_str()
-        .parse::<U>()
-        .unwrap()
+    let n = iter.next().unwrap();
+    number_regex.find(n).unwrap().as_str().parse::<U>().unwrap()
 }
 
-fn read_monkeys<T>(lines: Lines<T>) -> Vec<Monkey>
-where
-    T: BufRead,
-{
+fn read_monkeys(input: &str) -> Vec<Monkey> {
     let number_regex = Regex::new(r"\d+").unwrap();
     let operation_regex = Regex::new(r"new = old (\*|\+) (\d+|old)").unwrap();
-    let mut iter = lines;
+    let mut iter = input.lines();
     let mut monkeys = vec![];
 
     while iter.next().is_some() {
-        let items = iter.next().unwrap().unwrap();
+        let items = iter.next().unwrap();
         let items = number_regex
-            .find_iter(&items)
+            .find_iter(items)
             .map(|x| x.as_str().parse::<u64>().unwrap())
             .collect::<Vec<_>>();
-        let operation = iter.next().unwrap().unwrap();
-        let operation_capture = operation_regex.captures(&operation).unwrap();
+        let operation = iter.next().unwrap();
+        let operation_capture = operation_regex.captures(operation).unwrap();
         let op = operation_capture.get(1).unwrap().as_str();
         let v = operation_capture.get(2).unwrap().as_str();
         let operation = match &v {
@@ -109,9 +102,9 @@ where
                 }
             }
         };
-        let divisible = get_next_number::<T, u64>(&mut iter);
-        let if_true = get_next_number::<T, usize>(&mut iter);
-        let if_false = get_next_number::<T, usize>(&mut iter);
+        let divisible = get_next_number::<u64>(&mut iter);
+        let if_true = get_next_number::<usize>(&mut iter);
+        let if_false = get_next_number::<usize>(&mut iter);
 
         iter.next();
 
@@ -124,34 +117,50 @@ where
     monkeys
 }
 
-fn resolve<T>(lines: Lines<T>) -> (u64, u64)
+fn rounds<F>(monkeys: &mut [Monkey], count: usize, reduce: F)
 where
-    T: BufRead,
+    F: Fn(u64) -> u64,
 {
-    let mut monkeys_1 = read_monkeys(lines);
-    let mut monkeys_2 = monkeys_1.clone();
-    let len = monkeys_1.len();
+    let progress = Progress::new("rounds", Some(count as u64));
 
-    for i in 0..len * 20 {
-        let throws = monkeys_1[i % len].round(|x| x / 3);
+    for _ in 0..count {
+        let _span = trace::span("round");
+
+        progress.add(1);
+
+        for i in 0..monkeys.len() {
+            let throws = monkeys[i].round(&reduce);
 
-        for (item, index) in throws {
-            monkeys_1[index].items.push(item);
+            for (item, index) in throws {
+                monkeys[index].items.push(item);
+            }
         }
     }
+}
+
+fn resolve(input: &str) -> (u64, u64) {
+    let mut monkeys_1 = {
+        let _span = trace::span("parse");
+
+        read_monkeys(input)
+    };
+    let mut monkeys_2 = monkeys_1.clone();
+
+    let part1_span = trace::span("part1");
+
+    rounds(&mut monkeys_1, params::get(file!(), "rounds1"), |x| x / 3);
 
     let mut inspects_1 = monkeys_1.iter().map(|x| x.inspects).collect::<Vec<_>>();
     inspects_1.sort_by(|a, b| b.cmp(a));
 
-    let supermodulo: u64 = monkeys_2.iter().map(|m| m.divisible.0).product();
+    drop(part1_span);
 
-    for i in 0..len * 10000 {
-        let throws = monkeys_2[i % len].round(|x| x % supermodulo);
+    let _part2_span = trace::span("part2");
+    let supermodulo: u64 = monkeys_2.iter().map(|m| m.divisible.0).product();
 
-        for (item, index) in throws {
-            monkeys_2[index].items.push(item);
-        }
-    }
+    rounds(&mut monkeys_2, params::get(file!(), "rounds2"), |x| {
+        x % supermodulo
+    });
 
     // println!("{monkeys2:?}");
 
@@ -196,20 +205,127 @@ Monkey 3:
     If true: throw to monkey 0
     If false: throw to monkey 1
 ";
-    use std::io::Cursor;
 
-    let (part1, part2) = resolve(Cursor::new(TEST).lines());
+    let (part1, part2) = resolve(TEST);
 
     assert_eq!(part1, 10605);
     assert_eq!(part2, 2713310158);
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
+fn resolve_string(input: &str) -> (String, String) {
+    let solution = resolve(input);
     (solution.0.to_string(), solution.1.to_string())
 }
 
-inventory::submit! { advent_2022::Day::new(file!(), resolve_string) }
+// part1 divides the worry levels by 3, nothing prevents them to overflow
+fn part1_overflows(monkeys: &[Monkey]) -> bool {
+    let mut monkeys = monkeys.to_vec();
+    let len = monkeys.len();
+
+    for i in 0..len * 20 {
+        let items = std::mem::take(&mut monkeys[i % len].items);
+
+        for item in items {
+            let item = match monkeys[i % len].operation {
+                Operation::Multiply(v) => item.checked_mul(v),
+                Operation::Add(v) => item.checked_add(v),
+                Operation::Square() => item.checked_mul(item),
+            };
+            let Some(item) = item else {
+                return true;
+            };
+            let index = divise(item / 3, &monkeys[i % len].divisible);
+
+            monkeys[index].items.push(item / 3);
+        }
+    }
+    false
+}
+
+// `size` starting items, spread over 2 to 8 monkeys whose divisors are
+// distinct primes so that their product squared fits in a u64
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    const PRIMES: [u64; 8] = [2, 3, 5, 7, 11, 13, 17, 19];
+
+    let monkeys = loop {
+        let count = rng.gen_range(2..9);
+        let mut primes = PRIMES.to_vec();
+
+        primes.shuffle(rng);
+
+        let mut monkeys = (0..count)
+            .map(|i| {
+                let operation = match rng.gen_range(0..10) {
+                    0 => Operation::Square(),
+                    1..=4 => Operation::Multiply(rng.gen_range(2..20)),
+                    _ => Operation::Add(rng.gen_range(1..10)),
+                };
+                let if_true = (i + rng.gen_range(1..count)) % count;
+                let if_false = (i + rng.gen_range(1..count)) % count;
+
+                Monkey::new(vec![], operation, (primes[i], if_true, if_false))
+            })
+            .collect::<Vec<_>>();
+
+        for _ in 0..size {
+            monkeys[rng.gen_range(0..count)]
+                .items
+                .push(rng.gen_range(50..100));
+        }
+
+        if !part1_overflows(&monkeys) {
+            break monkeys;
+        }
+    };
+
+    monkeys
+        .iter()
+        .enumerate()
+        .map(|(i, m)| {
+            let items = m
+                .items
+                .iter()
+                .map(|i| i.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            let operation = match m.operation {
+                Operation::Multiply(v) => format!("* {v}"),
+                Operation::Add(v) => format!("+ {v}"),
+                Operation::Square() => String::from("* old"),
+            };
+            let (divisible, if_true, if_false) = m.divisible;
+
+            format!(
+                "Monkey {i}:
+  Starting items: {items}
+  Operation: new = old {operation}
+  Test: divisible by {divisible}
+    If true: throw to monkey {if_true}
+    If false: throw to monkey {if_false}
+"
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn check_generate() {
+    use rand::SeedableRng;
+
+    for seed in 0..10 {
+        let input = generate(&mut StdRng::seed_from_u64(seed), 20);
+        let monkeys = read_monkeys(&input);
+
+        assert_eq!(monkeys.iter().map(|m| m.items.len()).sum::<usize>(), 20);
+        resolve(&input);
+    }
+}
+
+inventory::submit! {
+    advent_2022::Day::new(file!(), resolve_string)
+        .title("Monkey in the Middle")
+}
+inventory::submit! { advent_2022::generate::Generator::new(file!(), generate, 100) }
+inventory::submit! { advent_2022::params::Param::new(file!(), "rounds1", "20", "rounds of part1") }
+inventory::submit! { advent_2022::params::Param::new(file!(), "rounds2", "10000", "rounds of part2") }
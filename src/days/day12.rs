@@ -1,10 +1,8 @@
-use std::collections::VecDeque;
-use std::io::{BufRead, Lines};
-use std::{thread, time::Duration};
+use advent_2022::search;
 
 fn neighbours(
     position: (usize, usize),
-    grid: &Vec<Vec<u8>>,
+    grid: &[Vec<u8>],
     compare: fn(u8, u8) -> bool,
 ) -> Vec<(usize, usize)> {
     let mut ret: Vec<(usize, usize)> = vec![];
@@ -30,31 +28,23 @@ fn neighbours(
 }
 
 #[allow(dead_code)]
-fn print_bfs(lengths: &Vec<Vec<usize>>) {
-    let h = lengths.len();
-    let w = lengths[0].len();
-
-    for r in lengths {
+fn print_path(grid: &[Vec<u8>], path: &[(usize, usize)]) {
+    for (y, r) in grid.iter().enumerate() {
         let mut row = String::from("");
-        let mut sep = String::from("");
 
-        for l in r {
-            if *l == h * w {
-                row.push_str("   |");
+        for (x, c) in r.iter().enumerate() {
+            if path.contains(&(x, y)) {
+                row.push('#');
             } else {
-                let s = format!("{l:3}|");
-                row.push_str(&s);
+                row.push((c + b'a') as char);
             }
-            sep.push_str("---+");
         }
         println!("{row}");
-        println!("{sep}");
     }
-    thread::sleep(Duration::from_millis(1));
 }
 
 fn bfs<F>(
-    grid: &Vec<Vec<u8>>,
+    grid: &[Vec<u8>],
     start: (usize, usize),
     finished: F,
     compare: fn(u8, u8) -> bool,
@@ -62,48 +52,23 @@ fn bfs<F>(
 where
     F: Fn((usize, usize)) -> bool,
 {
-    let h = grid.len();
-    let w = grid[0].len();
-    let mut queue = VecDeque::new();
-    let mut lengths = vec![vec![w * h; w]; h];
-
-    lengths[start.1][start.0] = 0;
-
-    queue.push_back(start);
-
-    while !queue.is_empty() {
-        let pos = queue.pop_front().unwrap();
-        let length = lengths[pos.1][pos.0];
+    let path = search::bfs(
+        start,
+        |&pos| neighbours(pos, grid, compare),
+        |&pos| finished(pos),
+    );
 
-        if finished(pos) {
-            return length;
-        }
-
-        for p in neighbours(pos, grid, compare).iter() {
-            let new_l = length + 1;
-            let l = lengths[p.1][p.0];
+    // print_path(grid, path.as_ref().unwrap());
 
-            if new_l < l {
-                lengths[p.1][p.0] = new_l;
-
-                queue.push_back(*p);
-            }
-        }
-        // print_bfs(&lengths);
-    }
-    0
+    path.map_or(0, |p| p.len() - 1)
 }
 
-fn resolve<T>(lines: Lines<T>) -> (usize, usize)
-where
-    T: BufRead,
-{
+fn resolve(input: &str) -> (usize, usize) {
     let mut grid = vec![];
     let mut start = (0, 0);
     let mut exit = (0, 0);
 
-    for line in lines {
-        let line = line.unwrap();
+    for line in input.lines() {
         let mut row = vec![];
 
         for (i, c) in line.chars().enumerate() {
@@ -133,20 +98,19 @@ abcryxxl
 accszExk
 acctuvwj
 abdefghi";
-    use std::io::Cursor;
 
-    let (part1, part2) = resolve(Cursor::new(TEST).lines());
+    let (part1, part2) = resolve(TEST);
 
     assert_eq!(part1, 31);
     assert_eq!(part2, 29);
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
+fn resolve_string(input: &str) -> (String, String) {
+    let solution = resolve(input);
     (solution.0.to_string(), solution.1.to_string())
 }
 
-inventory::submit! { advent_2022::Day::new(file!(), resolve_string) }
+inventory::submit! {
+    advent_2022::Day::new(file!(), resolve_string)
+        .title("Hill Climbing Algorithm")
+}
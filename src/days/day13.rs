@@ -1,5 +1,4 @@
 use std::cmp::Ordering;
-use std::io::{BufRead, Lines};
 use std::num::ParseIntError;
 use std::str::FromStr;
 
@@ -301,18 +300,15 @@ fn check_packet() {
     );
 }
 
-fn resolve<T>(lines: Lines<T>) -> (usize, usize)
-where
-    T: BufRead,
-{
-    let mut iter = lines.into_iter().peekable();
+fn resolve(input: &str) -> (usize, usize) {
+    let mut iter = input.lines().peekable();
     let mut part1 = vec![];
     let mut part2 = vec![];
     let mut packets = vec![];
 
     while iter.peek().is_some() {
-        let left = iter.next().unwrap().unwrap();
-        let right = iter.next().unwrap().unwrap();
+        let left = iter.next().unwrap();
+        let right = iter.next().unwrap();
 
         packets.push(left.parse::<Packet>().unwrap());
         packets.push(right.parse::<Packet>().unwrap());
@@ -374,20 +370,19 @@ fn check() {
 
 [1,[2,[3,[4,[5,6,7]]]],8,9]
 [1,[2,[3,[4,[5,6,0]]]],8,9]";
-    use std::io::Cursor;
 
-    let (part1, part2) = resolve(Cursor::new(TEST).lines());
+    let (part1, part2) = resolve(TEST);
 
     assert_eq!(part1, 13);
     assert_eq!(part2, 140);
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
+fn resolve_string(input: &str) -> (String, String) {
+    let solution = resolve(input);
     (solution.0.to_string(), solution.1.to_string())
 }
 
-inventory::submit! { advent_2022::Day::new(file!(), resolve_string) }
+inventory::submit! {
+    advent_2022::Day::new(file!(), resolve_string)
+        .title("Distress Signal")
+}
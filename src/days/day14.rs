@@ -1,6 +1,6 @@
+use advent_2022::simulation::{Simulation, Simulator};
 use regex::Regex;
 use std::collections::{HashMap, HashSet};
-use std::io::{BufRead, Lines};
 
 fn segments(point_a: (i32, i32), point_b: (i32, i32)) -> Vec<(i32, i32)> {
     let mut points = vec![];
@@ -23,28 +23,6 @@ fn segments(point_a: (i32, i32), point_b: (i32, i32)) -> Vec<(i32, i32)> {
     points
 }
 
-#[allow(dead_code)]
-fn print_hash(verticals: &HashMap<i32, Vec<i32>>, maxy: i32) {
-    for y in 0..maxy + 2 {
-        let mut row = String::from("");
-
-        for x in 404..594 {
-            let vertical = verticals.get(&x);
-
-            if let Some(vertical) = vertical {
-                if vertical.contains(&y) {
-                    row.push('#');
-                } else {
-                    row.push('.');
-                }
-            } else {
-                row.push('.');
-            }
-        }
-        println!("{row}");
-    }
-}
-
 fn get_floor(verticals: &mut HashMap<i32, Vec<i32>>, (x, y): (i32, i32)) -> Option<i32> {
     verticals
         .get_mut(&x)?
@@ -56,24 +34,25 @@ fn insert_sorted(sorted: &mut Vec<i32>, value: i32) {
     sorted.insert(sorted.binary_search(&value).unwrap_err(), value);
 }
 
-fn add_sand_part1(verticals: &mut HashMap<i32, Vec<i32>>, (x, y): (i32, i32)) -> Option<i32> {
+fn add_sand(verticals: &mut HashMap<i32, Vec<i32>>, (x, y): (i32, i32), floor: i32) -> (i32, i32) {
     let mut x = x;
-    let mut y = get_floor(verticals, (x, y))?;
+    let mut y = get_floor(verticals, (x, y)).unwrap_or(floor);
 
     loop {
-        let left = get_floor(verticals, (x - 1, y))?;
+        let left = get_floor(verticals, (x - 1, y)).unwrap_or(floor);
 
         if left == y {
             // can't go left, let's try right
-            let right = get_floor(verticals, (x + 1, y))?;
+            let right = get_floor(verticals, (x + 1, y)).unwrap_or(floor);
 
             if right == y {
                 // no left, no rigth, stay here
-                let vertical = verticals.get_mut(&x).unwrap();
-
-                insert_sorted(vertical, y - 1);
+                verticals
+                    .entry(x)
+                    .and_modify(|v| insert_sorted(v, y - 1))
+                    .or_insert(vec![y - 1]);
 
-                return Some(y - 1);
+                return (x, y - 1);
             } else {
                 x += 1;
                 y = right;
@@ -85,115 +64,222 @@ fn add_sand_part1(verticals: &mut HashMap<i32, Vec<i32>>, (x, y): (i32, i32)) ->
     }
 }
 
-fn add_sand_part2(
-    verticals: &mut HashMap<i32, Vec<i32>>,
-    (x, y): (i32, i32),
+struct Cave {
+    rocks: HashSet<(i32, i32)>,
+    verticals: HashMap<i32, Vec<i32>>,
     maxy: i32,
-) -> Option<i32> {
-    let mut x = x;
-    let mut y = get_floor(verticals, (x, y)).unwrap_or(maxy);
+    sand: usize,
+    last: Option<(i32, i32)>,
+    // grains at rest before the first one reaches the floor, i.e. before
+    // it would have fallen into the abyss
+    part1: Option<usize>,
+}
 
-    loop {
-        let left = get_floor(verticals, (x - 1, y)).unwrap_or(maxy);
+impl Cave {
+    fn new(input: &str) -> Self {
+        let point_regex = Regex::new(r"\d+,\d+").unwrap();
+        let mut rocks: HashSet<(i32, i32)> = HashSet::new();
+        let mut maxy = 0;
+
+        for line in input.lines() {
+            let points = point_regex
+                .find_iter(line)
+                .map(|x| {
+                    let mut split = x.as_str().split(',');
+                    let x = split.next().unwrap().parse::<i32>().unwrap();
+                    let y = split.next().unwrap().parse::<i32>().unwrap();
+
+                    maxy = i32::max(maxy, y);
+                    (x, y)
+                })
+                .collect::<Vec<_>>();
+
+            for i in 1..points.len() {
+                for p in segments(points[i - 1], points[i]) {
+                    rocks.insert(p);
+                }
+            }
+        }
 
-        if left == y {
-            // can't go left, let's try right
-            let right = get_floor(verticals, (x + 1, y)).unwrap_or(maxy);
+        let mut verticals: HashMap<i32, Vec<i32>> = HashMap::new();
 
-            if right == y {
-                // no left, no rigth, stay here
-                verticals
-                    .entry(x)
-                    .and_modify(|v| insert_sorted(v, y - 1))
-                    .or_insert(vec![y - 1]);
+        for &(x, y) in &rocks {
+            verticals
+                .entry(x)
+                .and_modify(|v| v.push(y))
+                .or_insert(vec![y]);
+        }
 
-                return Some(y - 1);
-            } else {
-                x += 1;
-                y = right;
-            }
-        } else {
-            x -= 1;
-            y = left;
+        verticals.values_mut().for_each(|x| x.sort());
+
+        Cave {
+            rocks,
+            verticals,
+            maxy,
+            sand: 0,
+            last: None,
+            part1: None,
         }
     }
 }
 
-fn resolve<T>(lines: Lines<T>) -> (usize, u32)
-where
-    T: BufRead,
-{
-    let point_regex = Regex::new(r"\d+,\d+").unwrap();
-    let mut rocks: HashSet<(i32, i32)> = HashSet::new();
-    let mut maxy = 0;
-
-    for line in lines {
-        let line = line.unwrap();
-
-        let points = point_regex
-            .find_iter(&line)
-            .map(|x| {
-                let mut split = x.as_str().split(',');
-                let x = split.next().unwrap().parse::<i32>().unwrap();
-                let y = split.next().unwrap().parse::<i32>().unwrap();
-
-                maxy = i32::max(maxy, y);
-                (x, y)
-            })
-            .collect::<Vec<_>>();
+impl Simulation for Cave {
+    fn step(&mut self) -> bool {
+        if self.last == Some((500, 0)) {
+            return false;
+        }
 
-        for i in 1..points.len() {
-            for p in segments(points[i - 1], points[i]) {
-                rocks.insert(p);
-            }
+        let p = add_sand(&mut self.verticals, (500, 0), self.maxy + 2);
+
+        if self.part1.is_none() && p.1 == self.maxy + 1 {
+            self.part1 = Some(self.sand);
         }
-    }
 
-    let mut verticals1: HashMap<i32, Vec<i32>> = HashMap::new();
+        self.sand += 1;
+        self.last = Some(p);
 
-    for (x, y) in rocks {
-        verticals1
-            .entry(x)
-            .and_modify(|v| v.push(y))
-            .or_insert(vec![y]);
+        true
     }
 
-    verticals1.values_mut().for_each(|x| x.sort());
-
-    let mut verticals2 = verticals1.clone();
+    fn render(&self) -> String {
+        let minx = *self.verticals.keys().min().unwrap_or(&500);
+        let maxx = *self.verticals.keys().max().unwrap_or(&500);
+        let mut s = String::from("");
+
+        for y in 0..self.maxy + 3 {
+            for x in minx - 1..maxx + 2 {
+                if y == self.maxy + 2 || self.rocks.contains(&(x, y)) {
+                    s.push('#');
+                } else if self.last == Some((x, y)) {
+                    s.push('@');
+                } else if self.verticals.get(&x).is_some_and(|v| v.contains(&y)) {
+                    s.push('o');
+                } else if (x, y) == (500, 0) {
+                    s.push('+');
+                } else {
+                    s.push('.');
+                }
+            }
+            s.push('\n');
+        }
+        s
+    }
 
-    let mut part1 = 0;
-    let mut part2 = 1;
+    fn counters(&self) -> Vec<(&'static str, i64)> {
+        let (x, y) = self.last.unwrap_or((500, 0));
 
-    while add_sand_part1(&mut verticals1, (500, 0)).is_some() {
-        part1 += 1;
+        vec![
+            ("sand", self.sand as i64),
+            ("x", x as i64),
+            ("y", y as i64),
+            ("part1", self.part1.map_or(-1, |p| p as i64)),
+        ]
     }
+}
 
-    while add_sand_part2(&mut verticals2, (500, 0), maxy + 2) != Some(0) {
-        part2 += 1;
-    }
+fn resolve(input: &str) -> (usize, usize) {
+    let mut cave = Cave::new(input);
+
+    while cave.step() {}
 
-    (part1, part2)
+    (cave.part1.unwrap_or(cave.sand), cave.sand)
 }
 
 #[test]
 fn check() {
     const TEST: &str = "498,4 -> 498,6 -> 496,6
 503,4 -> 502,4 -> 502,9 -> 494,9";
-    use std::io::Cursor;
 
-    let (part1, part2) = resolve(Cursor::new(TEST).lines());
+    let (part1, part2) = resolve(TEST);
 
     assert_eq!(part1, 24);
     assert_eq!(part2, 93);
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
+#[test]
+fn check_random() {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(14);
+
+    for _ in 0..100 {
+        let paths = (0..rng.gen_range(1..6))
+            .map(|_| {
+                let mut p = (rng.gen_range(485..515), rng.gen_range(1..20));
+                let mut path = vec![p];
+
+                for _ in 0..rng.gen_range(1..4) {
+                    if rng.gen() {
+                        p.0 = rng.gen_range(485..515);
+                    } else {
+                        p.1 = rng.gen_range(1..20);
+                    }
+                    path.push(p);
+                }
+                path
+            })
+            .collect::<Vec<Vec<(i32, i32)>>>();
+        let input = paths
+            .iter()
+            .map(|path| {
+                path.iter()
+                    .map(|(x, y)| format!("{x},{y}"))
+                    .collect::<Vec<_>>()
+                    .join(" -> ")
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        // dense grid, the floor is two rows below the lowest rock
+        let maxy = paths.iter().flatten().map(|p| p.1).max().unwrap() as usize;
+        let mut grid = vec![vec![false; 1000]; maxy + 3];
+
+        grid[maxy + 2].iter_mut().for_each(|c| *c = true);
+        for path in &paths {
+            for w in path.windows(2) {
+                for (x, y) in segments(w[0], w[1]) {
+                    grid[y as usize][x as usize] = true;
+                }
+            }
+        }
+
+        let mut part1 = None;
+        let mut part2 = 0;
+
+        while !grid[0][500] {
+            let (mut x, mut y) = (500, 0);
+
+            while let Some(dx) = [0, -1, 1]
+                .into_iter()
+                .find(|&dx| !grid[y + 1][(x as i32 + dx) as usize])
+            {
+                x = (x as i32 + dx) as usize;
+                y += 1;
+            }
+
+            if y > maxy && part1.is_none() {
+                part1 = Some(part2);
+            }
+            grid[y][x] = true;
+            part2 += 1;
+        }
+
+        assert_eq!(resolve(&input), (part1.unwrap_or(part2), part2), "{input}");
+    }
+}
+
+fn resolve_string(input: &str) -> (String, String) {
+    let solution = resolve(input);
     (solution.0.to_string(), solution.1.to_string())
 }
 
-inventory::submit! { advent_2022::Day::new(file!(), resolve_string) }
+inventory::submit! {
+    advent_2022::Day::new(file!(), resolve_string)
+        .title("Regolith Reservoir")
+}
+
+fn simulate(input: &str) -> Box<dyn Simulation> {
+    Box::new(Cave::new(input))
+}
+
+inventory::submit! { Simulator::new(file!(), simulate) }
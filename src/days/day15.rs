@@ -1,9 +1,9 @@
+use advent_2022::progress::Progress;
+use advent_2022::{params, trace};
+use rand::{rngs::StdRng, Rng};
 use rayon::prelude::*;
 use regex::Regex;
-use std::{
-    cmp::Ordering,
-    io::{BufRead, Lines},
-};
+use std::cmp::Ordering;
 
 #[derive(Debug)]
 struct Sensor {
@@ -100,7 +100,7 @@ fn merge_ranges(mut list: Vec<(i32, i32)>) -> Vec<(i32, i32)> {
 }
 
 fn ranges_on_row(
-    sensors: &Vec<Sensor>,
+    sensors: &[Sensor],
     row: i32,
     count_beacon: bool,
     delim: Option<&(i32, i32)>,
@@ -124,16 +124,65 @@ fn ranges_on_row(
     merge_ranges(ranges)
 }
 
-fn resolve<T>(lines: Lines<T>) -> (i32, i64)
-where
-    T: BufRead,
-{
-    let beacon_regex = Regex::new(r"-?\d+").unwrap();
-    let sensors = Vec::from_iter(lines.map(|line| {
-        let line = line.unwrap();
+#[test]
+fn check_random() {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use std::collections::HashSet;
+
+    let mut rng = StdRng::seed_from_u64(15);
+
+    for _ in 0..200 {
+        let sensors = (0..rng.gen_range(1..10))
+            .map(|_| {
+                let (x, y) = (rng.gen_range(-10..30), rng.gen_range(-10..30));
+
+                Sensor::new(x, y, x + rng.gen_range(-8..9), y + rng.gen_range(-8..9))
+            })
+            .collect::<Vec<_>>();
+        let delim = (rng.gen_range(-5..10), rng.gen_range(10..25));
+
+        for row in -20..40 {
+            for (count_beacon, delim) in [(false, None), (true, Some(&delim))] {
+                let (min, max) = delim.map_or((-50, 80), |&d| d);
+                let expected = (min..max + 1)
+                    .filter(|&x| {
+                        sensors.iter().any(|s| {
+                            (s.x - x).abs() + (s.y - row).abs() <= s.manhattan as i32
+                                && (count_beacon || (s.bx, s.by) != (x, row))
+                        })
+                    })
+                    .collect::<HashSet<_>>();
+
+                let ranges = ranges_on_row(&sensors, row, count_beacon, delim);
+                let marked = ranges
+                    .iter()
+                    .flat_map(|&(left, right)| left..right + 1)
+                    .collect::<Vec<_>>();
+
+                assert_eq!(marked.len(), expected.len(), "{sensors:?} row {row}");
+                assert!(marked.iter().all(|x| expected.contains(x)));
+            }
+        }
+    }
+}
 
+// row checked by part1 and size of the square searched by part2
+fn field() -> (i32, i32) {
+    (params::get(file!(), "row"), params::get(file!(), "size"))
+}
+
+fn resolve(input: &str) -> (i32, i64) {
+    let (row, size) = field();
+
+    resolve_field(input, row, size)
+}
+
+fn resolve_field(input: &str, row: i32, size: i32) -> (i32, i64) {
+    let parse_span = trace::span("parse");
+    let beacon_regex = Regex::new(r"-?\d+").unwrap();
+    let sensors = Vec::from_iter(input.lines().map(|line| {
         let mut beacon_values = beacon_regex
-            .find_iter(&line)
+            .find_iter(line)
             .map(|x| x.as_str().parse::<i32>().unwrap());
 
         let x = beacon_values.next().unwrap();
@@ -144,39 +193,47 @@ where
         Sensor::new(x, y, bx, by)
     }));
 
-    let row;
-    let size;
+    drop(parse_span);
 
-    if cfg!(test) {
-        row = 10;
-        size = 20;
-    } else {
-        row = 2000000;
-        size = 4000000;
-    }
-
-    let part2 = (0..size + 1)
-        //.into_iter()
-        //.find_map(|row| {
-        .into_par_iter()
-        .find_map_any(|row| {
-            let ranges = ranges_on_row(&sensors, row, true, Some(&(0, size)));
-
-            if ranges.len() == 2 {
-                Some((ranges[0].1 as i64 + 1) * 4000000 + row as i64)
-            } else {
-                None
-            }
-        })
-        .unwrap();
+    let part1 = {
+        let _span = trace::span("part1");
 
-    (
         ranges_on_row(&sensors, row, false, None)
             .iter()
             .map(|(left, right)| right - left + 1)
-            .sum(),
-        part2,
-    )
+            .sum()
+    };
+
+    let part2 = {
+        let _span = trace::span("part2");
+        let parent = trace::path();
+        let progress = Progress::new("rows", Some(size as u64 + 1));
+
+        (0..size + 1)
+            //.into_iter()
+            //.map(|row| {
+            .into_par_iter()
+            // a span for each run of rows scanned by a rayon thread, which
+            // counts them on its own
+            .map_init(
+                || (trace::span_in(&parent, "rows"), progress.batch()),
+                |(_span, rows), row| {
+                    let ranges = ranges_on_row(&sensors, row, true, Some(&(0, size)));
+
+                    rows.add(1);
+
+                    if ranges.len() == 2 {
+                        Some((ranges[0].1 as i64 + 1) * 4000000 + row as i64)
+                    } else {
+                        None
+                    }
+                },
+            )
+            .find_map_any(|part2| part2)
+            .unwrap()
+    };
+
+    (part1, part2)
 }
 
 #[test]
@@ -195,20 +252,78 @@ Sensor at x=17, y=20: closest beacon is at x=21, y=22
 Sensor at x=16, y=7: closest beacon is at x=15, y=3
 Sensor at x=14, y=3: closest beacon is at x=15, y=3
 Sensor at x=20, y=1: closest beacon is at x=15, y=3";
-    use std::io::Cursor;
 
-    let (part1, part2) = resolve(Cursor::new(TEST).lines());
+    let (part1, part2) = resolve_field(TEST, 10, 20);
 
     assert_eq!(part1, 26);
     assert_eq!(part2, 56000011);
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
+fn resolve_string(input: &str) -> (String, String) {
+    let solution = resolve(input);
     (solution.0.to_string(), solution.1.to_string())
 }
 
-inventory::submit! { advent_2022::Day::new(file!(), resolve_string) }
+// Four sensors far away along the diagonals leave only the gap uncovered,
+// it sits on the border of all their ranges. The other sensors have their
+// beacon closer than the gap.
+fn generate_field(rng: &mut StdRng, count: usize, size: i32) -> (String, (i32, i32)) {
+    let gap = (rng.gen_range(1..size), rng.gen_range(0..size + 1));
+    let far = 2 * size;
+    let mut sensors = vec![
+        ((gap.0 + far, gap.1 + far), (gap.0 + 1, gap.1)),
+        ((gap.0 - far, gap.1 - far), (gap.0 - 1, gap.1)),
+        ((gap.0 + far, gap.1 - far), (gap.0 + 1, gap.1)),
+        ((gap.0 - far, gap.1 + far), (gap.0 - 1, gap.1)),
+    ];
+
+    while sensors.len() < count {
+        let s = (rng.gen_range(0..size + 1), rng.gen_range(0..size + 1));
+        let distance = (s.0 - gap.0).abs() + (s.1 - gap.1).abs();
+
+        if distance < 2 {
+            continue;
+        }
+
+        let range = rng.gen_range(1..distance);
+        let dx = rng.gen_range(-range..range + 1);
+        let dy = (range - dx.abs()) * if rng.gen() { 1 } else { -1 };
+
+        sensors.push((s, (s.0 + dx, s.1 + dy)));
+    }
+
+    let input = sensors
+        .iter()
+        .map(|((x, y), (bx, by))| {
+            format!("Sensor at x={x}, y={y}: closest beacon is at x={bx}, y={by}\n")
+        })
+        .collect();
+
+    (input, gap)
+}
+
+// `size` sensors
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    generate_field(rng, size, field().1).0
+}
+
+#[test]
+fn check_generate() {
+    use rand::SeedableRng;
+
+    for seed in 0..10 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let (input, gap) = generate_field(&mut rng, 30, 20);
+        let (_, part2) = resolve_field(&input, 10, 20);
+
+        assert_eq!(part2, gap.0 as i64 * 4000000 + gap.1 as i64);
+    }
+}
+
+inventory::submit! {
+    advent_2022::Day::new(file!(), resolve_string)
+        .title("Beacon Exclusion Zone")
+}
+inventory::submit! { advent_2022::generate::Generator::new(file!(), generate, 30) }
+inventory::submit! { advent_2022::params::Param::new(file!(), "row", "2000000", "row checked by part1") }
+inventory::submit! { advent_2022::params::Param::new(file!(), "size", "4000000", "size of the square searched by part2") }
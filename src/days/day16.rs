@@ -1,6 +1,7 @@
+use advent_2022::{params, search, trace};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng};
 use regex::Regex;
 use std::collections::HashMap;
-use std::io::{BufRead, Lines};
 
 #[derive(Debug)]
 struct Valve {
@@ -34,34 +35,24 @@ fn is_disjoint(m1: Mask, m2: Mask) -> bool {
     (m1 & m2) == 0
 }
 
-// Floyd-Warshall
-fn compute_distances(valves: &Vec<Valve>) -> Vec<Vec<u32>> {
+fn compute_distances(valves: &[Valve]) -> Vec<Vec<u32>> {
     let names: HashMap<&String, usize> = valves
         .iter()
         .enumerate()
         .map(|(i, v)| (&v.name, i))
         .collect();
     let length = valves.len();
-    let mut distances = vec![vec![length as u32 + 1; length]; length];
 
-    for (i, v) in valves.iter().enumerate() {
-        v.neighbors
-            .iter()
-            .for_each(|n| distances[i][*(names.get(n).unwrap())] = 1);
-        distances[i][i] = 0;
-    }
-
-    for k in 0..length {
-        for i in 0..length {
-            for j in 0..length {
-                if distances[i][j] > distances[i][k] + distances[k][j] {
-                    distances[i][j] = distances[i][k] + distances[k][j];
-                }
-            }
-        }
-    }
-
-    distances
+    search::floyd_warshall(length, |i| {
+        valves[i].neighbors.iter().map(|n| (names[n], 1))
+    })
+    .into_iter()
+    .map(|row| {
+        row.into_iter()
+            .map(|d| d.unwrap_or(length as u32 + 1))
+            .collect()
+    })
+    .collect()
 }
 
 struct Volcano {
@@ -79,6 +70,12 @@ impl Volcano {
         }
     }
 
+    fn traced_dfs(&self, start: usize, time: u32) -> Vec<(Mask, u32)> {
+        let _span = trace::span("dfs");
+
+        self.dfs(start, 0, 0, 0, time)
+    }
+
     fn dfs(
         &self,
         current: usize,
@@ -116,17 +113,14 @@ impl Volcano {
     }
 }
 
-fn resolve<T>(lines: Lines<T>) -> (u32, u32)
-where
-    T: BufRead,
-{
+fn resolve(input: &str) -> (u32, u32) {
+    let parse_span = trace::span("parse");
     let valve_regex = Regex::new(
         r"Valve (\w+) has flow rate=(\d+); (?:tunnels lead to valves|tunnel leads to valve) (.*)",
     )
     .unwrap();
-    let valves = Vec::from_iter(lines.map(|line| {
-        let line = line.unwrap();
-        let valve_capture = valve_regex.captures(&line).unwrap();
+    let valves = Vec::from_iter(input.lines().map(|line| {
+        let valve_capture = valve_regex.captures(line).unwrap();
         let name = valve_capture.get(1).unwrap().as_str();
         let rate = valve_capture
             .get(2)
@@ -149,21 +143,34 @@ where
         panic!("too much valves for Mask");
     }
 
-    let distances = compute_distances(&valves);
+    drop(parse_span);
+
+    let distances = {
+        let _span = trace::span("distances");
+
+        compute_distances(&valves)
+    };
     let useful_valves_indexes = valves
         .iter()
         .enumerate()
         .filter_map(|(i, v)| if v.rate == 0 { None } else { Some(i) })
         .collect::<Vec<_>>();
-    let index_aa = valves.iter().position(|v| v.name == "AA").unwrap();
+    let start = params::get::<String>(file!(), "start");
+    let index_start = valves.iter().position(|v| v.name == start).unwrap();
     let volcano = Volcano::new(valves, useful_valves_indexes, distances);
 
+    let part1_span = trace::span("part1");
     let (_, part1) = *volcano
-        .dfs(index_aa, 0, 0, 0, 30)
+        .traced_dfs(index_start, params::get(file!(), "minutes1"))
         .iter()
         .max_by_key(|(_, f)| f)
         .unwrap();
-    let flows = volcano.dfs(index_aa, 0, 0, 0, 26);
+
+    drop(part1_span);
+
+    let _part2_span = trace::span("part2");
+    let flows = volcano.traced_dfs(index_start, params::get(file!(), "minutes2"));
+    let _pairs_span = trace::span("pairs");
 
     let mut part2 = 0;
     let (_, max_for_one) = flows.iter().max_by_key(|(_, f)| f).unwrap();
@@ -199,20 +206,94 @@ Valve GG has flow rate=0; tunnels lead to valves FF, HH
 Valve HH has flow rate=22; tunnel leads to valve GG
 Valve II has flow rate=0; tunnels lead to valves AA, JJ
 Valve JJ has flow rate=21; tunnel leads to valve II";
-    use std::io::Cursor;
 
-    let (part1, part2) = resolve(Cursor::new(TEST).lines());
+    let (part1, part2) = resolve(TEST);
 
     assert_eq!(part1, 1651);
     assert_eq!(part2, 1707);
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
+fn resolve_string(input: &str) -> (String, String) {
+    let solution = resolve(input);
     (solution.0.to_string(), solution.1.to_string())
 }
 
-inventory::submit! { advent_2022::Day::new(file!(), resolve_string) }
+// `size` valves (at most the 64 a Mask can hold) connected as a random tree
+// plus a few shortcuts, only a quarter of them (at most 15) are useful
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let size = size.clamp(2, 8 * std::mem::size_of::<Mask>());
+    let mut names = vec![String::from("AA")];
+
+    while names.len() < size {
+        let name = (0..2).map(|_| rng.gen_range('A'..='Z')).collect::<String>();
+
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names.shuffle(rng);
+
+    let mut neighbors = vec![vec![]; size];
+
+    for i in 1..size {
+        let j = rng.gen_range(0..i);
+
+        neighbors[i].push(j);
+        neighbors[j].push(i);
+    }
+    for _ in 0..size / 4 {
+        let (i, j) = (rng.gen_range(0..size), rng.gen_range(0..size));
+
+        if i != j && !neighbors[i].contains(&j) {
+            neighbors[i].push(j);
+            neighbors[j].push(i);
+        }
+    }
+
+    let mut rates = vec![0; size];
+    let mut useful = (0..size).filter(|&i| names[i] != "AA").collect::<Vec<_>>();
+
+    useful.shuffle(rng);
+    useful
+        .iter()
+        .take(usize::min(15, usize::max(1, size / 4)))
+        .for_each(|&i| rates[i] = rng.gen_range(1..26));
+
+    (0..size)
+        .map(|i| {
+            let tunnels = neighbors[i]
+                .iter()
+                .map(|&j| names[j].as_str())
+                .collect::<Vec<_>>();
+            let tunnels = if tunnels.len() == 1 {
+                format!("tunnel leads to valve {}", tunnels[0])
+            } else {
+                format!("tunnels lead to valves {}", tunnels.join(", "))
+            };
+
+            format!("Valve {} has flow rate={}; {tunnels}\n", names[i], rates[i])
+        })
+        .collect()
+}
+
+#[test]
+fn check_generate() {
+    use rand::SeedableRng;
+
+    for seed in 0..10 {
+        let input = generate(&mut StdRng::seed_from_u64(seed), 20);
+        let (part1, part2) = resolve(&input);
+
+        assert!(part1 > 0);
+        assert!(part2 > 0);
+    }
+}
+
+inventory::submit! {
+    advent_2022::Day::new(file!(), resolve_string)
+        .title("Proboscidea Volcanium")
+}
+inventory::submit! { advent_2022::generate::Generator::new(file!(), generate, 60) }
+inventory::submit! { advent_2022::params::Param::new(file!(), "start", "AA", "valve where both parts start") }
+inventory::submit! { advent_2022::params::Param::new(file!(), "minutes1", "30", "minutes of part1") }
+inventory::submit! { advent_2022::params::Param::new(file!(), "minutes2", "26", "minutes of part2, with the elephant") }
@@ -1,4 +1,10 @@
-use std::io::{BufRead, Lines};
+use advent_2022::params;
+use advent_2022::progress::Progress;
+use advent_2022::simulation::{Simulation, Simulator};
+use std::collections::HashMap;
+
+// rows below the surface telling two chambers apart
+const PROFILE_DEPTH: usize = 32;
 
 struct Chamber {
     grid: Vec<[bool; 7]>,
@@ -7,11 +13,11 @@ struct Chamber {
 }
 
 impl Chamber {
-    #[allow(dead_code)]
-    fn print(&self, points: Vec<(usize, usize)>) {
-        for (y, row) in self.grid.iter().enumerate().rev() {
-            let mut s = String::from("");
+    fn render(&self, points: Vec<(usize, usize)>, rows: usize) -> String {
+        let mut s = String::from("");
 
+        for (y, row) in self.grid.iter().enumerate().rev().take(rows) {
+            s.push('|');
             for (i, c) in row.iter().enumerate() {
                 if *c {
                     s.push('#');
@@ -21,9 +27,21 @@ impl Chamber {
                     s.push('.');
                 }
             }
-            println!("{s}");
+            s.push_str("|\n");
         }
-        println!();
+        s
+    }
+
+    // depth of the highest rock of each column, the rocks deeper don't change
+    // how the next ones fall
+    fn profile(&self) -> [usize; 7] {
+        std::array::from_fn(|x| {
+            (0..=self.height)
+                .rev()
+                .take(PROFILE_DEPTH)
+                .position(|y| self.grid[y][x])
+                .unwrap_or(PROFILE_DEPTH)
+        })
     }
 
     fn reserve(&mut self, height: usize) {
@@ -44,19 +62,18 @@ impl Chamber {
                 self.height = y;
             }
 
+            // the full row is kept as the floor, it may be the highest
             if (0..7).all(|x| self.grid[y][x]) {
-                // let l = self.grid.len();
-                self.grid.drain(..y+1);
-                // println!("{l} {y} new tall: {}", self.grid.len());
-                self.removed += y + 1;
-                self.height -= y + 1;
+                self.grid.drain(..y);
+                self.removed += y;
+                self.height -= y;
                 return;
             }
         }
     }
 }
 
-#[derive(PartialEq)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 enum Shape {
     Horizontal,
     Cross,
@@ -175,83 +192,165 @@ impl Tetris {
     }
 }
 
-fn resolve<T>(lines: Lines<T>) -> (usize, usize)
-where
-    T: BufRead,
-{
-    let mut chamber = Chamber {
-        grid: vec![[false; 7]; 4], height: 0, removed: 0,
-    };
-    let mut tetris = Tetris {
-        pos: (2, 3),
-        shape: Shape::Horizontal,
-    };
-    let jets = lines
-        .into_iter()
-        .next()
-        .unwrap()
-        .unwrap()
-        .chars()
-        .collect::<Vec<_>>();
-    let mut jet_index = 0;
-    let mut rocks = 0usize;
-
-    // chamber.print(tetris.points());
+struct Game {
+    chamber: Chamber,
+    tetris: Tetris,
+    jets: Vec<char>,
+    jet_index: usize,
+    rocks: usize,
+    // the simulation is over once that many rocks have fallen
+    last_rock: usize,
+}
 
-    loop {
-        let dir = jets[jet_index];
+impl Game {
+    fn new(input: &str, last_rock: usize) -> Self {
+        let jets = input.lines().next().unwrap().chars().collect::<Vec<_>>();
+
+        Game {
+            chamber: Chamber {
+                grid: vec![[false; 7]; 4],
+                height: 0,
+                removed: 0,
+            },
+            tetris: Tetris {
+                pos: (2, 3),
+                shape: Shape::Horizontal,
+            },
+            jets,
+            jet_index: 0,
+            rocks: 0,
+            last_rock,
+        }
+    }
 
-        jet_index = (jet_index + 1) % jets.len();
+    // the chamber height is the row of the highest rock, when there is one
+    fn height(&self) -> usize {
+        if self.rocks == 0 {
+            0
+        } else {
+            self.chamber.height + self.chamber.removed + 1
+        }
+    }
+
+    fn drop_rock(&mut self) {
+        let rocks = self.rocks;
+
+        self.run_until(|g| g.rocks > rocks);
+    }
+}
+
+impl Simulation for Game {
+    fn step(&mut self) -> bool {
+        if self.rocks >= self.last_rock {
+            return false;
+        }
+
+        let dir = self.jets[self.jet_index];
+
+        self.jet_index = (self.jet_index + 1) % self.jets.len();
 
         if dir == '<' {
-            tetris.move_left(&chamber);
+            self.tetris.move_left(&self.chamber);
         } else {
-            tetris.move_right(&chamber);
+            self.tetris.move_right(&self.chamber);
         }
 
-        // chamber.print(tetris.points());
+        if !self.tetris.move_down(&self.chamber) {
+            self.chamber.add_points(self.tetris.points());
 
-        if !tetris.move_down(&chamber) {
-            chamber.add_points(tetris.points());
+            self.rocks += 1;
 
-            rocks += 1;
-            if rocks == 2022 {
-                break;
-            }
+            self.tetris.shape = self.tetris.shape.next();
 
-            tetris.shape = tetris.shape.next();
+            self.chamber
+                .reserve(self.chamber.height + 4 + self.tetris.shape.height());
 
-            if jet_index == 0 && tetris.shape == Shape::Horizontal {
-                println!("loop");
-            }
+            self.tetris.pos = (2, self.chamber.height + 4);
+        }
+        true
+    }
+
+    fn render(&self) -> String {
+        self.chamber.render(self.tetris.points(), 40)
+    }
 
-            chamber.reserve(chamber.height + 4 + tetris.shape.height());
+    fn counters(&self) -> Vec<(&'static str, i64)> {
+        vec![
+            ("rocks", self.rocks as i64),
+            ("height", self.height() as i64),
+            ("jet", self.jet_index as i64),
+        ]
+    }
+}
 
-            tetris.pos = (2, chamber.height + 4);
+// the heights after each rock until the chamber is back in a state already
+// seen, the rocks then fall again as they did since
+fn find_cycle(game: &mut Game) -> (Vec<usize>, usize) {
+    let progress = Progress::new("rocks", None);
+    let mut heights = vec![0];
+    let mut seen = HashMap::new();
+
+    loop {
+        game.drop_rock();
+        heights.push(game.height());
+        progress.set(game.rocks as u64);
+
+        let state = (game.tetris.shape, game.jet_index, game.chamber.profile());
+
+        if let Some(start) = seen.insert(state, game.rocks) {
+            return (heights, start);
         }
-        //chamber.print(tetris.points());
     }
+}
 
-    (chamber.height + chamber.removed + 1, 0)
+fn height_after(heights: &[usize], start: usize, rocks: usize) -> usize {
+    if rocks < heights.len() {
+        return heights[rocks];
+    }
+
+    let end = heights.len() - 1;
+    let (cycles, rest) = (
+        (rocks - start) / (end - start),
+        (rocks - start) % (end - start),
+    );
+
+    heights[start + rest] + cycles * (heights[end] - heights[start])
+}
+
+fn resolve(input: &str) -> (usize, usize) {
+    let mut game = Game::new(input, usize::MAX);
+    let (heights, start) = find_cycle(&mut game);
+
+    (
+        height_after(&heights, start, params::get(file!(), "rocks")),
+        height_after(&heights, start, params::get(file!(), "rocks2")),
+    )
 }
 
 #[test]
 fn check() {
     const TEST: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
-    use std::io::Cursor;
 
-    let (part1, _part2) = resolve(Cursor::new(TEST).lines());
+    let (part1, part2) = resolve(TEST);
 
     assert_eq!(part1, 3068);
-//    assert_eq!(part2, 1514285714288);
+    assert_eq!(part2, 1514285714288);
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
+fn resolve_string(input: &str) -> (String, String) {
+    let solution = resolve(input);
     (solution.0.to_string(), solution.1.to_string())
 }
 
-inventory::submit! { advent_2022::Day::new(file!(), resolve_string) }
+inventory::submit! {
+    advent_2022::Day::new(file!(), resolve_string)
+        .title("Pyroclastic Flow")
+}
+
+fn simulate(input: &str) -> Box<dyn Simulation> {
+    Box::new(Game::new(input, params::get(file!(), "rocks")))
+}
+
+inventory::submit! { Simulator::new(file!(), simulate) }
+inventory::submit! { advent_2022::params::Param::new(file!(), "rocks", "2022", "rocks fallen for part1") }
+inventory::submit! { advent_2022::params::Param::new(file!(), "rocks2", "1000000000000", "rocks fallen for part2") }
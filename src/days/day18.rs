@@ -1,6 +1,6 @@
+use rand::{rngs::StdRng, Rng};
 use regex::Regex;
 use std::collections::HashSet;
-use std::io::{BufRead, Lines};
 
 fn get_neighbor(p: (i32, i32, i32)) -> [(i32, i32, i32); 6] {
     [
@@ -57,16 +57,13 @@ fn capture_point(re: &regex::Regex, line: &str) -> (i32, i32, i32) {
     (x, y, z)
 }
 
-fn resolve<T>(lines: Lines<T>) -> (usize, usize)
-where
-    T: BufRead,
-{
+fn resolve(input: &str) -> (usize, usize) {
     let point_regex = Regex::new(r"(\d+),(\d+),(\d+)").unwrap();
     let mut points = HashSet::new();
     let mut max = (0, 0, 0);
 
-    for line in lines {
-        let p = capture_point(&point_regex, &line.unwrap());
+    for line in input.lines() {
+        let p = capture_point(&point_regex, line);
 
         max.0 = i32::max(max.0, p.0);
         max.1 = i32::max(max.1, p.1);
@@ -142,20 +139,51 @@ fn check() {
 3,2,5
 2,1,5
 2,3,5";
-    use std::io::Cursor;
 
-    let (part1, part2) = resolve(Cursor::new(TEST).lines());
+    let (part1, part2) = resolve(TEST);
 
     assert_eq!(part1, 64);
     assert_eq!(part2, 58);
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
+fn resolve_string(input: &str) -> (String, String) {
+    let solution = resolve(input);
     (solution.0.to_string(), solution.1.to_string())
 }
 
-inventory::submit! { advent_2022::Day::new(file!(), resolve_string) }
+// `size` cubes filling about half of a box, which leaves air pockets
+fn generate(rng: &mut StdRng, size: usize) -> String {
+    let side = f64::cbrt(2.0 * size as f64).ceil() as i32 + 1;
+    let mut cubes = HashSet::new();
+    let mut input = String::new();
+
+    while cubes.len() < size {
+        let cube = (
+            rng.gen_range(1..side),
+            rng.gen_range(1..side),
+            rng.gen_range(1..side),
+        );
+
+        if cubes.insert(cube) {
+            input.push_str(&format!("{},{},{}\n", cube.0, cube.1, cube.2));
+        }
+    }
+
+    input
+}
+
+#[test]
+fn check_generate() {
+    use rand::SeedableRng;
+
+    let input = generate(&mut StdRng::seed_from_u64(18), 1000);
+    let (part1, part2) = resolve(&input);
+
+    assert!(part2 < part1);
+}
+
+inventory::submit! {
+    advent_2022::Day::new(file!(), resolve_string)
+        .title("Boiling Boulders")
+}
+inventory::submit! { advent_2022::generate::Generator::new(file!(), generate, 3000) }
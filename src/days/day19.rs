@@ -0,0 +1,219 @@
+use advent_2022::progress::Progress;
+use advent_2022::{params, trace};
+use rayon::prelude::*;
+use regex::Regex;
+
+const ORE: usize = 0;
+const CLAY: usize = 1;
+const OBSIDIAN: usize = 2;
+const GEODE: usize = 3;
+
+#[derive(Debug)]
+struct Blueprint {
+    id: u32,
+    // ore, clay and obsidian needed by each robot
+    costs: [[u32; 3]; 4],
+    // the most of each resource a robot costs, no more can be spent a minute
+    max_spend: [u32; 3],
+}
+
+#[derive(Clone, Copy)]
+struct State {
+    remaining: u32,
+    robots: [u32; 4],
+    resources: [u32; 4],
+}
+
+impl Blueprint {
+    fn new(values: &[u32]) -> Self {
+        let costs = [
+            [values[1], 0, 0],
+            [values[2], 0, 0],
+            [values[3], values[4], 0],
+            [values[5], 0, values[6]],
+        ];
+        let max_spend = [ORE, CLAY, OBSIDIAN].map(|r| costs.iter().map(|c| c[r]).max().unwrap());
+
+        Blueprint {
+            id: values[0],
+            costs,
+            max_spend,
+        }
+    }
+
+    // minutes until the resources for `robot` are collected
+    fn wait(&self, state: &State, robot: usize) -> Option<u32> {
+        (ORE..=OBSIDIAN)
+            .map(|r| {
+                let cost = self.costs[robot][r];
+
+                if state.resources[r] >= cost {
+                    Some(0)
+                } else if state.robots[r] == 0 {
+                    None
+                } else {
+                    Some((cost - state.resources[r]).div_ceil(state.robots[r]))
+                }
+            })
+            .try_fold(0, |wait, w| Some(u32::max(wait, w?)))
+    }
+
+    // no need for another robot once the stock and production cover the most
+    // expensive spending of every remaining minute
+    fn is_enough(&self, state: &State, robot: usize) -> bool {
+        robot != GEODE
+            && state.robots[robot] * state.remaining + state.resources[robot]
+                >= self.max_spend[robot] * state.remaining
+    }
+
+    fn max_geodes(&self, minutes: u32) -> u32 {
+        let mut best = 0;
+        let start = State {
+            remaining: minutes,
+            robots: [1, 0, 0, 0],
+            resources: [0; 4],
+        };
+
+        self.dfs(&start, &mut best);
+        best
+    }
+
+    // jumps from a robot built to the next one, the most valuable first to
+    // raise `best` early
+    fn dfs(&self, state: &State, best: &mut u32) {
+        let geodes = state.resources[GEODE] + state.robots[GEODE] * state.remaining;
+
+        *best = u32::max(*best, geodes);
+
+        // a geode robot built every remaining minute
+        let bound = geodes + state.remaining * state.remaining.saturating_sub(1) / 2;
+
+        if bound <= *best {
+            return;
+        }
+
+        for robot in (ORE..=GEODE).rev() {
+            if self.is_enough(state, robot) {
+                continue;
+            }
+
+            let Some(wait) = self.wait(state, robot) else {
+                continue;
+            };
+            let minutes = wait + 1;
+
+            // built in the last minute, it would collect nothing
+            if minutes >= state.remaining {
+                continue;
+            }
+
+            let mut next = *state;
+
+            next.remaining -= minutes;
+            for r in ORE..=GEODE {
+                next.resources[r] += state.robots[r] * minutes;
+            }
+            for r in ORE..=OBSIDIAN {
+                next.resources[r] -= self.costs[robot][r];
+            }
+            next.robots[robot] += 1;
+
+            self.dfs(&next, best);
+        }
+    }
+}
+
+// the numbers are enough, the example has line breaks inside blueprints
+fn read_blueprints(input: &str) -> Vec<Blueprint> {
+    let number_regex = Regex::new(r"\d+").unwrap();
+    let values = number_regex
+        .find_iter(input)
+        .map(|m| m.as_str().parse::<u32>().unwrap())
+        .collect::<Vec<_>>();
+
+    values.chunks_exact(7).map(Blueprint::new).collect()
+}
+
+fn max_geodes(blueprints: &[Blueprint], minutes: u32) -> Vec<u32> {
+    let parent = trace::path();
+    let progress = Progress::new("blueprints", Some(blueprints.len() as u64));
+
+    blueprints
+        .par_iter()
+        .map(|blueprint| {
+            let _span = trace::span_in(&parent, format!("blueprint{}", blueprint.id));
+            let geodes = blueprint.max_geodes(minutes);
+
+            progress.add(1);
+            geodes
+        })
+        .collect()
+}
+
+fn resolve(input: &str) -> (u32, u32) {
+    let blueprints = {
+        let _span = trace::span("parse");
+
+        read_blueprints(input)
+    };
+
+    let part1 = {
+        let _span = trace::span("part1");
+
+        max_geodes(&blueprints, params::get(file!(), "minutes1"))
+            .iter()
+            .zip(&blueprints)
+            .map(|(geodes, blueprint)| blueprint.id * geodes)
+            .sum()
+    };
+
+    let part2 = {
+        let _span = trace::span("part2");
+        let count = usize::min(blueprints.len(), params::get(file!(), "blueprints2"));
+
+        max_geodes(&blueprints[..count], params::get(file!(), "minutes2"))
+            .iter()
+            .product()
+    };
+
+    (part1, part2)
+}
+
+#[test]
+fn check() {
+    const TEST: &str = "Blueprint 1:
+  Each ore robot costs 4 ore.
+  Each clay robot costs 2 ore.
+  Each obsidian robot costs 3 ore and 14 clay.
+  Each geode robot costs 2 ore and 7 obsidian.
+
+Blueprint 2:
+  Each ore robot costs 2 ore.
+  Each clay robot costs 3 ore.
+  Each obsidian robot costs 3 ore and 8 clay.
+  Each geode robot costs 3 ore and 12 obsidian.";
+
+    let blueprints = read_blueprints(TEST);
+
+    assert_eq!(blueprints.len(), 2);
+    assert_eq!(max_geodes(&blueprints, 24), [9, 12]);
+    assert_eq!(max_geodes(&blueprints, 32), [56, 62]);
+
+    let (part1, part2) = resolve(TEST);
+
+    assert_eq!(part1, 33);
+    assert_eq!(part2, 56 * 62);
+}
+
+fn resolve_string(input: &str) -> (String, String) {
+    let solution = resolve(input);
+    (solution.0.to_string(), solution.1.to_string())
+}
+
+inventory::submit! {
+    advent_2022::Day::new(file!(), resolve_string)
+        .title("Not Enough Minerals")
+}
+inventory::submit! { advent_2022::params::Param::new(file!(), "minutes1", "24", "minutes of part1") }
+inventory::submit! { advent_2022::params::Param::new(file!(), "minutes2", "32", "minutes of part2") }
+inventory::submit! { advent_2022::params::Param::new(file!(), "blueprints2", "3", "first blueprints used by part2") }
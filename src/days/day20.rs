@@ -1,4 +1,4 @@
-use std::io::{BufRead, Lines};
+use advent_2022::{params, trace};
 
 #[derive(Clone, Copy, Debug)]
 struct Elem {
@@ -28,7 +28,9 @@ fn move_step(indexes: &[Elem], index: usize, forward: bool, count: usize) -> usi
     v
 }
 
-fn mix(indexes: &mut Vec<Elem>, values: &[i64], key: i64) {
+fn mix(indexes: &mut [Elem], values: &[i64], key: i64) {
+    let _span = trace::span("mix");
+
     for (index, value) in values.iter().enumerate() {
         let forward;
         let mut steps = (value * key).rem_euclid(indexes.len() as i64 - 1) as usize;
@@ -74,16 +76,60 @@ fn create_indexes(length: usize) -> Vec<Elem> {
         .collect()
 }
 
-fn resolve<T>(lines: Lines<T>) -> (i64, i64)
-where
-    T: BufRead,
-{
+#[test]
+fn check_random() {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(20);
+
+    for _ in 0..500 {
+        let length = rng.gen_range(2..40);
+        let values = (0..length)
+            .map(|_| rng.gen_range(-100..100))
+            .collect::<Vec<i64>>();
+        let key = [1, 811589153][rng.gen_range(0..2)];
+        let rounds = rng.gen_range(1..4);
+
+        // naive: the original indexes are moved around in a plain Vec
+        let mut expected = Vec::from_iter(0..length);
+        for _ in 0..rounds {
+            for (index, value) in values.iter().enumerate() {
+                let pos = expected.iter().position(|&i| i == index).unwrap();
+
+                expected.remove(pos);
+
+                let new_pos = (pos as i64 + value * key).rem_euclid(length as i64 - 1);
+
+                expected.insert(new_pos as usize, index);
+            }
+        }
+        let start = expected.iter().position(|&i| i == 0).unwrap();
+        expected.rotate_left(start);
+
+        let mut indexes = create_indexes(length);
+        for _ in 0..rounds {
+            mix(&mut indexes, &values, key);
+        }
+        let mixed = (0..length)
+            .scan(0, |v, _| {
+                let current = *v;
+                *v = indexes[current].next;
+                Some(current)
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(mixed, expected, "{values:?} key {key} rounds {rounds}");
+    }
+}
+
+fn resolve(input: &str) -> (i64, i64) {
+    let parse_span = trace::span("parse");
     let mut values = vec![];
     let mut v0_index = 0;
-    let key = 811589153;
+    let key = params::get::<i64>(file!(), "key");
 
-    for line in lines {
-        let n = line.unwrap().parse::<i64>().unwrap();
+    for line in input.lines() {
+        let n = line.parse::<i64>().unwrap();
 
         if n == 0 {
             v0_index = values.len();
@@ -92,6 +138,9 @@ where
         values.push(n);
     }
 
+    drop(parse_span);
+
+    let part1_span = trace::span("part1");
     let mut indexes = create_indexes(values.len());
     mix(&mut indexes, &values, 1);
 
@@ -100,6 +149,9 @@ where
     let v3000_index = move_step(&indexes, v2000_index, true, 1000);
     let part1 = values[v1000_index] + values[v2000_index] + values[v3000_index];
 
+    drop(part1_span);
+
+    let _part2_span = trace::span("part2");
     indexes = create_indexes(values.len());
     for _ in 0..10 {
         mix(&mut indexes, &values, key);
@@ -122,20 +174,20 @@ fn check() {
 -2
 0
 4";
-    use std::io::Cursor;
 
-    let (part1, part2) = resolve(Cursor::new(TEST).lines());
+    let (part1, part2) = resolve(TEST);
 
     assert_eq!(part1, 3);
     assert_eq!(part2, 1623178306);
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
+fn resolve_string(input: &str) -> (String, String) {
+    let solution = resolve(input);
     (solution.0.to_string(), solution.1.to_string())
 }
 
-inventory::submit! { advent_2022::Day::new(file!(), resolve_string) }
+inventory::submit! {
+    advent_2022::Day::new(file!(), resolve_string)
+        .title("Grove Positioning System")
+}
+inventory::submit! { advent_2022::params::Param::new(file!(), "key", "811589153", "decryption key of part2") }
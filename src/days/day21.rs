@@ -1,5 +1,4 @@
 use std::collections::HashMap;
-use std::io::{BufRead, Lines};
 use std::str::FromStr;
 
 #[derive(Debug, Clone)]
@@ -52,34 +51,34 @@ fn find_operation(hash: &HashMap<String, Operation>, name: &String) -> (String,
         match v {
             Operation::Add(a, b) if a == name => {
                 return (k.clone(), Operation::Del(k.clone(), b.clone()));
-            },
+            }
             Operation::Add(a, b) if b == name => {
                 return (k.clone(), Operation::Del(k.clone(), a.clone()));
-            },
+            }
             Operation::Del(a, b) if a == name => {
                 return (k.clone(), Operation::Add(k.clone(), b.clone()));
-            },
+            }
             Operation::Del(a, b) if b == name => {
                 return (k.clone(), Operation::Del(a.clone(), k.clone()));
-            },
+            }
             Operation::Product(a, b) if a == name => {
                 return (k.clone(), Operation::Divide(k.clone(), b.clone()));
-            },
+            }
             Operation::Product(a, b) if b == name => {
                 return (k.clone(), Operation::Divide(k.clone(), a.clone()));
-            },
+            }
             Operation::Divide(a, b) if a == name => {
                 return (k.clone(), Operation::Product(k.clone(), b.clone()));
-            },
+            }
             Operation::Divide(a, b) if b == name => {
                 return (k.clone(), Operation::Divide(a.clone(), k.clone()));
-            },
+            }
             Operation::Equal(a, b) if a == name => {
                 return (k.clone(), Operation::Alias(b.clone()));
-            },
+            }
             Operation::Equal(a, b) if b == name => {
                 return (k.clone(), Operation::Alias(a.clone()));
-            },
+            }
             _ => continue,
         }
     }
@@ -103,7 +102,7 @@ fn compute(hash: &mut HashMap<String, Operation>, name: &String) -> i64 {
         Operation::Del(a, b) => compute(hash, &a) - compute(hash, &b),
         Operation::Product(a, b) => compute(hash, &a) * compute(hash, &b),
         Operation::Divide(a, b) => compute(hash, &a) / compute(hash, &b),
-        Operation::Equal(_, _)  => panic!(),
+        Operation::Equal(_, _) => panic!(),
         Operation::Alias(s) => compute(hash, &s),
         Operation::Value(v) => v,
     };
@@ -112,14 +111,10 @@ fn compute(hash: &mut HashMap<String, Operation>, name: &String) -> i64 {
     result
 }
 
-fn resolve<T>(lines: Lines<T>) -> (i64, i64)
-where
-    T: BufRead,
-{
+fn resolve(input: &str) -> (i64, i64) {
     let mut hash = HashMap::new();
 
-    for line in lines {
-        let line = line.unwrap();
+    for line in input.lines() {
         let splits: Vec<&str> = line.split(": ").collect();
 
         hash.insert(
@@ -159,20 +154,19 @@ pppw: cczh / lfqf
 lgvd: ljgn * ptdq
 drzm: hmdt - zczc
 hmdt: 32";
-    use std::io::Cursor;
 
-    let (part1, part2) = resolve(Cursor::new(TEST).lines());
+    let (part1, part2) = resolve(TEST);
 
     assert_eq!(part1, 152);
     assert_eq!(part2, 301);
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
+fn resolve_string(input: &str) -> (String, String) {
+    let solution = resolve(input);
     (solution.0.to_string(), solution.1.to_string())
 }
 
-inventory::submit! { advent_2022::Day::new(file!(), resolve_string) }
+inventory::submit! {
+    advent_2022::Day::new(file!(), resolve_string)
+        .title("Monkey Math")
+}
@@ -0,0 +1,395 @@
+use advent_2022::trace;
+use std::collections::VecDeque;
+
+type Vector = [i32; 3];
+
+fn neg(v: Vector) -> Vector {
+    v.map(|x| -x)
+}
+
+fn dot(a: Vector, b: Vector) -> i32 {
+    (0..3).map(|i| a[i] * b[i]).sum()
+}
+
+// right, down, left and up as (row, column) steps, indexed by the facing
+const STEPS: [(i32, i32); 4] = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+
+#[derive(Debug, PartialEq)]
+enum Move {
+    Forward(usize),
+    Left,
+    Right,
+}
+
+fn read_path(line: &str) -> Vec<Move> {
+    let mut path = vec![];
+    let mut count = 0;
+
+    for c in line.trim().chars() {
+        if let Some(digit) = c.to_digit(10) {
+            count = count * 10 + digit as usize;
+            continue;
+        }
+        if count > 0 {
+            path.push(Move::Forward(count));
+            count = 0;
+        }
+        match c {
+            'L' => path.push(Move::Left),
+            'R' => path.push(Move::Right),
+            _ => panic!("unknown move {c}"),
+        }
+    }
+    if count > 0 {
+        path.push(Move::Forward(count));
+    }
+    path
+}
+
+#[test]
+fn check_path() {
+    assert_eq!(
+        read_path("10R5L12"),
+        [
+            Move::Forward(10),
+            Move::Right,
+            Move::Forward(5),
+            Move::Left,
+            Move::Forward(12)
+        ]
+    );
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Position {
+    row: usize,
+    column: usize,
+    facing: usize,
+}
+
+impl Position {
+    fn password(&self) -> usize {
+        1000 * (self.row + 1) + 4 * (self.column + 1) + self.facing
+    }
+}
+
+struct Board {
+    tiles: Vec<Vec<u8>>,
+}
+
+impl Board {
+    fn new(map: &str) -> Self {
+        Board {
+            tiles: map.lines().map(|line| line.as_bytes().to_vec()).collect(),
+        }
+    }
+
+    // a space outside of the lines
+    fn tile(&self, row: i32, column: i32) -> u8 {
+        if row < 0 || column < 0 {
+            return b' ';
+        }
+        self.tiles
+            .get(row as usize)
+            .and_then(|line| line.get(column as usize))
+            .copied()
+            .unwrap_or(b' ')
+    }
+
+    fn walk<F>(&self, path: &[Move], wrap: F) -> Position
+    where
+        F: Fn(Position) -> Position,
+    {
+        let mut position = Position {
+            row: 0,
+            column: self.tiles[0].iter().position(|&t| t == b'.').unwrap(),
+            facing: 0,
+        };
+
+        for m in path {
+            match m {
+                Move::Left => position.facing = (position.facing + 3) % 4,
+                Move::Right => position.facing = (position.facing + 1) % 4,
+                Move::Forward(count) => {
+                    for _ in 0..*count {
+                        let (dr, dc) = STEPS[position.facing];
+                        let (row, column) = (position.row as i32 + dr, position.column as i32 + dc);
+                        let next = if self.tile(row, column) == b' ' {
+                            wrap(position)
+                        } else {
+                            Position {
+                                row: row as usize,
+                                column: column as usize,
+                                ..position
+                            }
+                        };
+
+                        if self.tiles[next.row][next.column] == b'#' {
+                            break;
+                        }
+                        position = next;
+                    }
+                }
+            }
+        }
+        position
+    }
+
+    // back to the first tile on the other side of the row or column
+    fn wrap_flat(&self, position: Position) -> Position {
+        let (dr, dc) = STEPS[position.facing];
+        let (mut row, mut column) = (position.row as i32, position.column as i32);
+
+        while self.tile(row - dr, column - dc) != b' ' {
+            row -= dr;
+            column -= dc;
+        }
+        Position {
+            row: row as usize,
+            column: column as usize,
+            ..position
+        }
+    }
+}
+
+// a square of the board with the directions of its normal, columns and rows
+// once folded
+#[derive(Clone, Copy, Debug)]
+struct Face {
+    block: (i32, i32),
+    normal: Vector,
+    right: Vector,
+    down: Vector,
+}
+
+struct Cube {
+    size: i32,
+    faces: Vec<Face>,
+}
+
+impl Cube {
+    // the first face is laid flat, each of its neighbors on the board is turned
+    // a quarter around their shared edge and so on, whatever the net
+    fn fold(board: &Board) -> Self {
+        let tiles = board.tiles.iter().flatten().filter(|&&t| t != b' ').count();
+        let size = (1..).find(|s| 6 * s * s >= tiles).unwrap();
+
+        assert_eq!(6 * size * size, tiles, "the board is not a cube net");
+
+        let size = size as i32;
+        let height = board.tiles.len() as i32 / size;
+        let width = board.tiles.iter().map(|l| l.len()).max().unwrap() as i32 / size;
+        let blocks = (0..height)
+            .flat_map(|r| (0..width).map(move |c| (r, c)))
+            .filter(|&(r, c)| board.tile(r * size, c * size) != b' ')
+            .collect::<Vec<_>>();
+        let mut faces = vec![Face {
+            block: blocks[0],
+            normal: [0, 0, 1],
+            right: [1, 0, 0],
+            down: [0, 1, 0],
+        }];
+        let mut queue = VecDeque::from([0]);
+
+        while let Some(i) = queue.pop_front() {
+            let face = faces[i];
+
+            for (facing, (dr, dc)) in STEPS.iter().enumerate() {
+                let block = (face.block.0 + dr, face.block.1 + dc);
+
+                if !blocks.contains(&block) || faces.iter().any(|f| f.block == block) {
+                    continue;
+                }
+
+                let folded = match facing {
+                    0 => Face {
+                        normal: face.right,
+                        right: neg(face.normal),
+                        ..face
+                    },
+                    1 => Face {
+                        normal: face.down,
+                        down: neg(face.normal),
+                        ..face
+                    },
+                    2 => Face {
+                        normal: neg(face.right),
+                        right: face.normal,
+                        ..face
+                    },
+                    _ => Face {
+                        normal: neg(face.down),
+                        down: face.normal,
+                        ..face
+                    },
+                };
+
+                faces.push(Face { block, ..folded });
+                queue.push_back(faces.len() - 1);
+            }
+        }
+        Cube { size, faces }
+    }
+
+    // in doubled coordinates from the center of the cube, the faces are at
+    // +-size and their tiles two units apart: stepping over an edge
+    // moves a unit along the heading onto the next face and a unit back from
+    // the face left, which becomes the new heading
+    fn wrap(&self, position: Position) -> Position {
+        let n = self.size;
+        let (row, column) = (position.row as i32, position.column as i32);
+        let from = self
+            .faces
+            .iter()
+            .find(|f| f.block == (row / n, column / n))
+            .unwrap();
+        let headings = |f: &Face| [f.right, f.down, neg(f.right), neg(f.down)];
+        let heading = headings(from)[position.facing];
+        let to = self.faces.iter().find(|f| f.normal == heading).unwrap();
+        let point: Vector = std::array::from_fn(|i| {
+            n * from.normal[i]
+                + (2 * (column % n) - n + 1) * from.right[i]
+                + (2 * (row % n) - n + 1) * from.down[i]
+                + heading[i]
+                - from.normal[i]
+        });
+
+        Position {
+            row: (to.block.0 * n + (dot(point, to.down) + n - 1) / 2) as usize,
+            column: (to.block.1 * n + (dot(point, to.right) + n - 1) / 2) as usize,
+            facing: headings(to)
+                .iter()
+                .position(|&h| h == neg(from.normal))
+                .unwrap(),
+        }
+    }
+}
+
+fn resolve(input: &str) -> (usize, usize) {
+    let (board, path) = {
+        let _span = trace::span("parse");
+        let (map, path) = input.split_once("\n\n").unwrap();
+
+        (Board::new(map), read_path(path))
+    };
+
+    let part1 = {
+        let _span = trace::span("part1");
+
+        board.walk(&path, |p| board.wrap_flat(p)).password()
+    };
+
+    let part2 = {
+        let _span = trace::span("part2");
+        let cube = Cube::fold(&board);
+
+        board.walk(&path, |p| cube.wrap(p)).password()
+    };
+
+    (part1, part2)
+}
+
+#[test]
+fn check() {
+    const TEST: &str = "        ...#
+        .#..
+        #...
+        ....
+...#.......#
+........#...
+..#....#....
+..........#.
+        ...#....
+        .....#..
+        .#......
+        ......#.
+
+10R5L5R10L4R5L5";
+
+    let (part1, part2) = resolve(TEST);
+
+    assert_eq!(part1, 6032);
+    assert_eq!(part2, 5031);
+}
+
+#[test]
+fn check_nets() {
+    const NETS: [&[&str]; 11] = [
+        &["#", "####", "#"],
+        &["#", "####", " #"],
+        &["#", "####", "  #"],
+        &["#", "####", "   #"],
+        &[" #", "####", " #"],
+        &[" #", "####", "  #"],
+        &["##", " ###", " #"],
+        &["##", " ###", "  #"],
+        &["##", " ###", "   #"],
+        &["##", " ##", "  ##"],
+        &["###", "  ###"],
+    ];
+
+    // as wide as the example and the real inputs
+    for (size, net) in [4, 50].into_iter().flat_map(|s| NETS.map(|n| (s, n))) {
+        let map = net
+            .iter()
+            .flat_map(|line| {
+                let tiles = line
+                    .chars()
+                    .map(|c| if c == '#' { "." } else { " " }.repeat(size))
+                    .collect::<String>();
+
+                vec![tiles; size]
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let board = Board::new(&map);
+        let cube = Cube::fold(&board);
+        let mut normals = cube.faces.iter().map(|f| f.normal).collect::<Vec<_>>();
+
+        normals.sort();
+        normals.dedup();
+        assert_eq!(normals.len(), 6, "{map}");
+
+        // stepping back over an edge returns where it started
+        for (row, line) in board.tiles.iter().enumerate() {
+            for column in (0..line.len()).filter(|&c| line[c] == b'.') {
+                for (facing, (dr, dc)) in STEPS.iter().enumerate() {
+                    if board.tile(row as i32 + dr, column as i32 + dc) != b' ' {
+                        continue;
+                    }
+
+                    let position = Position {
+                        row,
+                        column,
+                        facing,
+                    };
+                    let wrapped = cube.wrap(position);
+                    let back = cube.wrap(Position {
+                        facing: (wrapped.facing + 2) % 4,
+                        ..wrapped
+                    });
+
+                    assert_eq!(board.tiles[wrapped.row][wrapped.column], b'.', "{map}");
+                    assert_eq!(
+                        back,
+                        Position {
+                            facing: (facing + 2) % 4,
+                            ..position
+                        },
+                        "{map}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn resolve_string(input: &str) -> (String, String) {
+    let solution = resolve(input);
+    (solution.0.to_string(), solution.1.to_string())
+}
+
+inventory::submit! {
+    advent_2022::Day::new(file!(), resolve_string)
+        .title("Monkey Map")
+}
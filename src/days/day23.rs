@@ -1,6 +1,8 @@
+use advent_2022::progress::Progress;
+use advent_2022::simulation::{Simulation, Simulator};
+use advent_2022::trace;
 use rayon::prelude::*;
 use std::collections::{HashMap, HashSet};
-use std::io::{BufRead, Lines};
 
 #[derive(Debug, Copy, Clone)]
 enum Direction {
@@ -39,33 +41,6 @@ impl Direction {
     }
 }
 
-#[allow(dead_code)]
-fn print(points: &HashSet<(i32, i32)>) {
-    let mut iter = points.iter();
-    let mut min = *iter.next().unwrap();
-    let mut max = min;
-
-    for &(x, y) in iter {
-        min.0 = i32::min(min.0, x);
-        min.1 = i32::min(min.1, y);
-        max.0 = i32::max(max.0, x);
-        max.1 = i32::max(max.1, y);
-    }
-
-    println!("min: {min:?}");
-    for y in min.1..max.1 + 1 {
-        let mut line = String::from("");
-        for x in min.0..max.0 + 1 {
-            if points.contains(&(x, y)) {
-                line.push('#');
-            } else {
-                line.push('.');
-            }
-        }
-        println!("{line}");
-    }
-}
-
 fn get_neighbor(p: &(i32, i32)) -> [(i32, i32); 8] {
     [
         (p.0 - 1, p.1 - 1),
@@ -102,9 +77,15 @@ fn next_position(
 }
 
 fn update_points(points: &mut HashSet<(i32, i32)>, start: Direction) -> bool {
+    let parent = trace::path();
     let new_points = points
         .par_iter()
-        .filter_map(|p| next_position(p, points, start).map(|new_p| (new_p, p)))
+        // a span for each run of elves handled by a rayon thread
+        .map_init(
+            || trace::span_in(&parent, "elves"),
+            |_span, p| next_position(p, points, start).map(|new_p| (new_p, p)),
+        )
+        .flatten()
         .collect::<Vec<_>>()
         .iter()
         .fold(
@@ -144,43 +125,108 @@ fn part1(points: &HashSet<(i32, i32)>) -> i32 {
     (1 + max.1 - min.1) * (1 + max.0 - min.0) - points.len() as i32
 }
 
-fn resolve<T>(lines: Lines<T>) -> (i32, u32)
-where
-    T: BufRead,
-{
-    let mut points = HashSet::new();
+struct Grove {
+    points: HashSet<(i32, i32)>,
+    direction: Direction,
+    round: u32,
+}
 
-    for (y, line) in lines.enumerate() {
-        let line = line.unwrap();
+impl Grove {
+    fn new(input: &str) -> Self {
+        let mut points = HashSet::new();
 
-        for (x, c) in line.chars().enumerate() {
-            if c == '#' {
-                points.insert((x as i32, y as i32));
+        for (y, line) in input.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                if c == '#' {
+                    points.insert((x as i32, y as i32));
+                }
             }
         }
+
+        Grove {
+            points,
+            direction: Direction::N,
+            round: 0,
+        }
+    }
+}
+
+impl Simulation for Grove {
+    fn step(&mut self) -> bool {
+        let _span = trace::span("round");
+        let moved = update_points(&mut self.points, self.direction);
+
+        // the round without any move is the last one, not counted
+        if moved {
+            self.direction = self.direction.next();
+            self.round += 1;
+        }
+        moved
     }
 
-    //print(&points);
+    fn render(&self) -> String {
+        let mut iter = self.points.iter();
+        let mut min = *iter.next().unwrap();
+        let mut max = min;
 
-    let mut direction = Direction::N;
-    let mut count = 10;
+        for &(x, y) in iter {
+            min.0 = i32::min(min.0, x);
+            min.1 = i32::min(min.1, y);
+            max.0 = i32::max(max.0, x);
+            max.1 = i32::max(max.1, y);
+        }
 
-    for _ in 0..count {
-        update_points(&mut points, direction);
+        let mut s = format!("min: {min:?}\n");
 
-        direction = direction.next();
+        for y in min.1..max.1 + 1 {
+            for x in min.0..max.0 + 1 {
+                if self.points.contains(&(x, y)) {
+                    s.push('#');
+                } else {
+                    s.push('.');
+                }
+            }
+            s.push('\n');
+        }
+        s
     }
-    //print(&points);
 
-    let part1 = part1(&points);
+    fn counters(&self) -> Vec<(&'static str, i64)> {
+        vec![
+            ("round", self.round as i64),
+            ("empty", part1(&self.points) as i64),
+        ]
+    }
+}
+
+fn resolve(input: &str) -> (i32, u32) {
+    let mut grove = {
+        let _span = trace::span("parse");
+
+        Grove::new(input)
+    };
+
+    let part1_span = trace::span("part1");
+
+    // the elves may stop moving before the 10th round
+    let moving = grove.run_until(|g| g.round == 10);
+    let part1 = part1(&grove.points);
 
-    while update_points(&mut points, direction) {
-        count += 1;
+    drop(part1_span);
 
-        direction = direction.next();
+    let part2_span = trace::span("part2");
+    let progress = Progress::new("rounds", None);
+
+    if moving {
+        while grove.step() {
+            progress.set(grove.round as u64);
+        }
     }
 
-    (part1, count + 1)
+    drop(part2_span);
+
+    // the first round without any move
+    (part1, grove.round + 1)
 }
 
 #[test]
@@ -197,20 +243,33 @@ fn check() {
 ..............
 ..............
 ..............";
-    use std::io::Cursor;
 
-    let (part1, part2) = resolve(Cursor::new(TEST).lines());
+    let (part1, part2) = resolve(TEST);
 
     assert_eq!(part1, 110);
     assert_eq!(part2, 20);
 }
 
-fn resolve_string<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
-    let solution = resolve(lines);
+#[test]
+fn check_settled() {
+    let (part1, part2) = resolve("#....\n.....\n....#");
+
+    assert_eq!(part1, 13);
+    assert_eq!(part2, 1);
+}
+
+fn resolve_string(input: &str) -> (String, String) {
+    let solution = resolve(input);
     (solution.0.to_string(), solution.1.to_string())
 }
 
-inventory::submit! { advent_2022::Day::new(file!(), resolve_string) }
+inventory::submit! {
+    advent_2022::Day::new(file!(), resolve_string)
+        .title("Unstable Diffusion")
+}
+
+fn simulate(input: &str) -> Box<dyn Simulation> {
+    Box::new(Grove::new(input))
+}
+
+inventory::submit! { Simulator::new(file!(), simulate) }
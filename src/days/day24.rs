@@ -0,0 +1,161 @@
+use advent_2022::trace;
+
+type Row = u128;
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+// the valley without its walls, a bit per column of each row
+struct Basin {
+    width: usize,
+    height: usize,
+    entrance: usize,
+    exit: usize,
+    // the blizzards at each minute of their period
+    blizzards: Vec<Vec<Row>>,
+}
+
+impl Basin {
+    fn new(input: &str) -> Self {
+        let lines = input.lines().map(str::as_bytes).collect::<Vec<_>>();
+        let (first, last) = (lines[0], lines[lines.len() - 1]);
+        let inner = &lines[1..lines.len() - 1];
+        let width = first.len() - 2;
+        let height = inner.len();
+
+        assert!(width < Row::BITS as usize, "the valley is too wide");
+
+        let heading = |blizzard| {
+            inner
+                .iter()
+                .map(|line| {
+                    (0..width)
+                        .filter(|&c| line[c + 1] == blizzard)
+                        .fold(0, |row: Row, c| row | 1 << c)
+                })
+                .collect::<Vec<_>>()
+        };
+        let (right, left, up, down) = (heading(b'>'), heading(b'<'), heading(b'^'), heading(b'v'));
+        let full = (1 << width) - 1;
+        // towards the higher columns
+        let rotate = |row: Row, shift: usize| (row << shift | row >> (width - shift)) & full;
+        let period = width * height / gcd(width, height);
+        let blizzards = (0..period)
+            .map(|t| {
+                (0..height)
+                    .map(|r| {
+                        rotate(right[r], t % width)
+                            | rotate(left[r], (width - t % width) % width)
+                            | up[(r + t) % height]
+                            | down[(r + height - t % height) % height]
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Basin {
+            width,
+            height,
+            entrance: first.iter().position(|&b| b == b'.').unwrap() - 1,
+            exit: last.iter().position(|&b| b == b'.').unwrap() - 1,
+            blizzards,
+        }
+    }
+
+    // minute of arrival, every position reachable is expanded at once and
+    // waiting at the start is always possible
+    fn cross(&self, start: usize, downwards: bool) -> usize {
+        let (from, to) = if downwards {
+            ((0, self.entrance), (self.height - 1, self.exit))
+        } else {
+            ((self.height - 1, self.exit), (0, self.entrance))
+        };
+        let full: Row = (1 << self.width) - 1;
+        let mut reached = vec![0; self.height];
+        let mut time = start;
+
+        loop {
+            time += 1;
+
+            if reached[to.0] & 1 << to.1 != 0 {
+                return time;
+            }
+
+            let blizzards = &self.blizzards[time % self.blizzards.len()];
+
+            reached = (0..self.height)
+                .map(|r| {
+                    let mut row = reached[r] | reached[r] << 1 | reached[r] >> 1;
+
+                    if r > 0 {
+                        row |= reached[r - 1];
+                    }
+                    if r + 1 < self.height {
+                        row |= reached[r + 1];
+                    }
+                    if r == from.0 {
+                        row |= 1 << from.1;
+                    }
+                    row & full & !blizzards[r]
+                })
+                .collect();
+        }
+    }
+}
+
+fn resolve(input: &str) -> (usize, usize) {
+    let basin = {
+        let _span = trace::span("parse");
+
+        Basin::new(input)
+    };
+
+    let part1 = {
+        let _span = trace::span("part1");
+
+        basin.cross(0, true)
+    };
+
+    let part2 = {
+        let _span = trace::span("part2");
+
+        basin.cross(basin.cross(part1, false), true)
+    };
+
+    (part1, part2)
+}
+
+#[test]
+fn check() {
+    const TEST: &str = "#.######
+#>>.<^<#
+#.<..<<#
+#>v.><>#
+#<^v^^>#
+######.#";
+
+    let basin = Basin::new(TEST);
+
+    assert_eq!(basin.blizzards.len(), 12);
+    assert_eq!(basin.blizzards[1][0], 0b010110);
+
+    let (part1, part2) = resolve(TEST);
+
+    assert_eq!(part1, 18);
+    assert_eq!(part2, 54);
+}
+
+fn resolve_string(input: &str) -> (String, String) {
+    let solution = resolve(input);
+    (solution.0.to_string(), solution.1.to_string())
+}
+
+inventory::submit! {
+    advent_2022::Day::new(file!(), resolve_string)
+        .title("Blizzard Basin")
+}
@@ -1,5 +1,3 @@
-use std::io::{BufRead, Lines};
-
 fn to_value(c: char) -> i32 {
     match c {
         '=' => -2,
@@ -55,14 +53,11 @@ fn check_add() {
     assert_eq!(add_snafu("1=11-2", "1-0"), "1=12=2");
 }
 
-fn resolve<T>(lines: Lines<T>) -> (String, String)
-where
-    T: BufRead,
-{
+fn resolve(input: &str) -> (String, String) {
     (
-        lines.fold(String::from("0"), |sum, line| {
-            add_snafu(&sum, &line.unwrap())
-        }),
+        input
+            .lines()
+            .fold(String::from("0"), |sum, line| add_snafu(&sum, line)),
         String::from(""),
     )
 }
@@ -82,11 +77,14 @@ fn check() {
 12
 1=
 122";
-    use std::io::Cursor;
 
-    let (part1, _) = resolve(Cursor::new(TEST).lines());
+    let (part1, _) = resolve(TEST);
 
     assert_eq!(part1, "2=-1=0");
 }
 
-inventory::submit! { advent_2022::Day::new(file!(), resolve) }
+inventory::submit! {
+    advent_2022::Day::new(file!(), resolve)
+        .title("Full of Hot Air")
+        .parts(1)
+}
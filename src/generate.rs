@@ -0,0 +1,74 @@
+use crate::{answers_path, input_path, is_compiled_out, parse_day_number, unavailable};
+use rand::{rngs::StdRng, SeedableRng};
+use std::fs;
+use std::path::Path;
+
+pub struct Generator {
+    day_filename: &'static str,
+    generate: fn(&mut StdRng, usize) -> String,
+    default_size: usize,
+}
+
+impl Generator {
+    /// `generate` builds a valid input from the random generator, `size`
+    /// being a day specific scale (number of moves, of directories...).
+    pub const fn new(
+        day_filename: &'static str,
+        generate: fn(&mut StdRng, usize) -> String,
+        default_size: usize,
+    ) -> Self {
+        Generator {
+            day_filename,
+            generate,
+            default_size,
+        }
+    }
+
+    pub(crate) fn parse_number(&self) -> u32 {
+        parse_day_number(self.day_filename)
+    }
+}
+
+inventory::collect!(Generator);
+
+/// Generates an input for `day_number`, printed on stdout or written where
+/// the runner reads it. An input already there, likely the real one, is only
+/// replaced with `force`.
+pub fn generate(day_number: u32, seed: u64, size: Option<usize>, write: bool, force: bool) {
+    let Some(generator) = inventory::iter::<Generator>
+        .into_iter()
+        .find(|g| g.parse_number() == day_number)
+    else {
+        if is_compiled_out(day_number) {
+            eprintln!("{}", unavailable(day_number));
+        } else {
+            eprintln!("day{day_number:0>2} has no generator");
+        }
+        return;
+    };
+    let path = input_path(day_number);
+
+    if write && !force && Path::new(&path).exists() {
+        eprintln!(
+            "{path} already exists, use --force to overwrite it or --inputs to write elsewhere"
+        );
+        return;
+    }
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let input = (generator.generate)(&mut rng, size.unwrap_or(generator.default_size));
+
+    if write {
+        if let Some(dir) = Path::new(&path).parent() {
+            fs::create_dir_all(dir).unwrap();
+        }
+        fs::write(&path, input).unwrap();
+        eprintln!("wrote {path}");
+
+        if Path::new(&answers_path(day_number)).exists() {
+            eprintln!("{} doesn't match anymore", answers_path(day_number));
+        }
+    } else {
+        print!("{input}");
+    }
+}
@@ -0,0 +1,252 @@
+use crate::params;
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// one line per resolved day: date, commit, profile, day and nanoseconds
+const HISTORY_PATH: &str = "./history.txt";
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+struct Record {
+    date: String,
+    commit: String,
+    profile: String,
+    day_number: u32,
+    duration: Duration,
+}
+
+impl Record {
+    fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.split('\t');
+        let record = Record {
+            date: fields.next()?.to_string(),
+            commit: fields.next()?.to_string(),
+            profile: fields.next()?.to_string(),
+            day_number: fields.next()?.strip_prefix("day")?.parse().ok()?,
+            duration: Duration::from_nanos(fields.next()?.parse().ok()?),
+        };
+
+        Some(record)
+    }
+}
+
+fn profile() -> &'static str {
+    if cfg!(debug_assertions) {
+        "debug"
+    } else {
+        "release"
+    }
+}
+
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn commit() -> String {
+    let Some(commit) = git(&["rev-parse", "--short", "HEAD"]) else {
+        return "unknown".to_string();
+    };
+
+    // timings of uncommitted changes are not the ones of the commit
+    if git(&["status", "--porcelain", "--untracked-files=no"]).is_some_and(|s| !s.is_empty()) {
+        format!("{commit}-dirty")
+    } else {
+        commit
+    }
+}
+
+// UTC, from the days to civil algorithm of Howard Hinnant
+fn format_date(seconds: u64) -> String {
+    let days = (seconds / 86400) as i64 + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    let time = seconds % 86400;
+
+    format!(
+        "{year:0>4}-{month:0>2}-{day:0>2}T{:0>2}:{:0>2}:{:0>2}Z",
+        time / 3600,
+        time / 60 % 60,
+        time % 60
+    )
+}
+
+/// Timings are appended to the history file from now on.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Appends the timings of a run to the history file, when enabled. The days
+/// run with overridden parameters are left out, their timings are not
+/// comparable.
+pub fn record(timings: &[(u32, Duration)]) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+
+    let timings = timings
+        .iter()
+        .filter(|(day_number, _)| params::overridden(*day_number).is_empty())
+        .collect::<Vec<_>>();
+
+    if timings.is_empty() {
+        return;
+    }
+
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let date = format_date(seconds);
+    let commit = commit();
+    let lines = timings
+        .iter()
+        .map(|(day_number, duration)| {
+            format!(
+                "{date}\t{commit}\t{}\tday{day_number:0>2}\t{}\n",
+                profile(),
+                duration.as_nanos()
+            )
+        })
+        .collect::<String>();
+
+    let written = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(HISTORY_PATH)
+        .and_then(|mut file| file.write_all(lines.as_bytes()));
+
+    if let Err(e) = written {
+        eprintln!("cannot write {HISTORY_PATH}: {e}");
+    }
+}
+
+fn read_records() -> Vec<Record> {
+    let history = fs::read_to_string(HISTORY_PATH).unwrap_or_default();
+
+    history
+        .lines()
+        .filter_map(Record::parse)
+        .filter(|r| r.profile == profile())
+        .collect()
+}
+
+/// Last recorded timing of each day.
+pub fn last_timings() -> BTreeMap<u32, Duration> {
+    read_records()
+        .into_iter()
+        .map(|r| (r.day_number, r.duration))
+        .collect()
+}
+
+fn median(mut durations: Vec<Duration>) -> Option<Duration> {
+    durations.sort_unstable();
+
+    match durations.len() {
+        0 => None,
+        n if n % 2 == 1 => Some(durations[n / 2]),
+        n => Some((durations[n / 2 - 1] + durations[n / 2]) / 2),
+    }
+}
+
+/// Compares the last recorded timing of `days` (all recorded days if empty)
+/// with the median of the timings of the `baseline` commit, or of the
+/// `window` previous ones, and flags the days slower by more than
+/// `threshold` times.
+pub fn report(days: &[u32], baseline: Option<&str>, window: usize, threshold: f64) {
+    let records = read_records();
+    let mut days = if days.is_empty() {
+        records.iter().map(|r| r.day_number).collect()
+    } else {
+        days.to_vec()
+    };
+
+    days.sort_unstable();
+    days.dedup();
+
+    if days.is_empty() {
+        println!("no {} timing recorded in {HISTORY_PATH}", profile());
+        return;
+    }
+
+    for day_number in days {
+        let day_records = records
+            .iter()
+            .filter(|r| r.day_number == day_number)
+            .collect::<Vec<_>>();
+        let Some((last, previous)) = day_records.split_last() else {
+            println!("day{day_number:0>2}: no timing recorded");
+            continue;
+        };
+        let (reference, against) = match baseline {
+            Some(baseline) => (
+                median(
+                    previous
+                        .iter()
+                        .filter(|r| r.commit == baseline)
+                        .map(|r| r.duration)
+                        .collect(),
+                ),
+                format!("baseline {baseline}"),
+            ),
+            None => {
+                let window = &previous[previous.len().saturating_sub(window)..];
+
+                (
+                    median(window.iter().map(|r| r.duration).collect()),
+                    format!("median of {}", window.len()),
+                )
+            }
+        };
+        let Some(reference) = reference else {
+            println!(
+                "day{day_number:0>2}: {:?} on {} at {}, nothing to compare with",
+                last.duration, last.commit, last.date
+            );
+            continue;
+        };
+        let ratio = last.duration.as_secs_f64() / reference.as_secs_f64();
+
+        println!(
+            "day{day_number:0>2}: {:?} on {} against {reference:?} ({against}) {ratio:.2}x{}",
+            last.duration,
+            last.commit,
+            if ratio > threshold { " SLOWER" } else { "" }
+        );
+    }
+}
+
+#[test]
+fn check_format_date() {
+    assert_eq!(format_date(0), "1970-01-01T00:00:00Z");
+    assert_eq!(format_date(951782400), "2000-02-29T00:00:00Z");
+    assert_eq!(format_date(1671926399), "2022-12-24T23:59:59Z");
+}
+
+#[test]
+fn check_median() {
+    let ms = Duration::from_millis;
+
+    assert_eq!(median(vec![]), None);
+    assert_eq!(median(vec![ms(3), ms(1), ms(2)]), Some(ms(2)));
+    assert_eq!(median(vec![ms(4), ms(1), ms(2), ms(8)]), Some(ms(3)));
+}
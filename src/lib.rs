@@ -1,58 +1,242 @@
+use serde_json::{json, Value};
+use std::any::Any;
 use std::cmp::{Eq, Ord, Ordering};
-use std::fs::File;
-use std::io::{self, BufRead, BufReader, Lines};
-use std::path::Path;
-use std::time::Instant;
+use std::fs;
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering as AtomicOrdering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// the days refer to the library by its name, as when they were compiled
+// into the executable
+extern crate self as advent_2022;
+
+pub mod cache;
+pub mod compare;
+pub mod config;
+mod days;
+pub mod generate;
+pub mod history;
+pub mod list;
+pub mod normalize;
+pub mod params;
+pub mod progress;
+pub mod scaling;
+pub mod search;
+pub mod serve;
+pub mod simulation;
+pub mod trace;
+pub mod watch;
 
 #[derive(Eq)]
 pub struct Day {
     day_filename: &'static str,
-    resolve: fn(Lines<BufReader<File>>) -> (String, String),
+    variant: Option<&'static str>,
+    title: &'static str,
+    parts: u32,
+    padded: bool,
+    resolve: fn(&str) -> (String, String),
 }
 
 impl Day {
-    pub const fn new(
+    /// `resolve` borrows the whole input, read once and normalized, to
+    /// iterate its `lines()` or its bytes without copying them.
+    pub const fn new(day_filename: &'static str, resolve: fn(&str) -> (String, String)) -> Self {
+        Day {
+            day_filename,
+            variant: None,
+            title: "",
+            parts: 2,
+            padded: false,
+            resolve,
+        }
+    }
+
+    /// Alternative implementation of a day, only run by `compare`.
+    pub const fn variant(
         day_filename: &'static str,
-        resolve: fn(Lines<BufReader<File>>) -> (String, String),
+        name: &'static str,
+        resolve: fn(&str) -> (String, String),
     ) -> Self {
         Day {
             day_filename,
+            variant: Some(name),
+            title: "",
+            parts: 2,
+            padded: false,
             resolve,
         }
     }
 
-    fn print(&self) {
+    /// Title of the puzzle, shown by `list`.
+    pub const fn title(self, title: &'static str) -> Self {
+        Day { title, ..self }
+    }
+
+    /// Number of parts implemented, when it isn't both.
+    pub const fn parts(self, parts: u32) -> Self {
+        Day { parts, ..self }
+    }
+
+    /// The lines of the input are padded with spaces, which are not worth a
+    /// warning.
+    pub const fn padded(self) -> Self {
+        Day {
+            padded: true,
+            ..self
+        }
+    }
+
+    // normalized, with a warning for anything suspicious
+    fn read_input(&self, day_number: u32, raw: String) -> String {
+        let input = normalize::normalize(raw);
+
+        for warning in normalize::warnings(&input, self.padded) {
+            eprintln!("day{day_number:0>2}: warning: {warning}");
+        }
+        input
+    }
+
+    fn name(&self) -> &'static str {
+        self.variant.unwrap_or("default")
+    }
+
+    fn timed_resolve(&self) -> (u32, String, String, Duration) {
         let start = Instant::now();
         let (day_number, part1, part2) = self.resolve();
-        let duration = start.elapsed();
 
-        println!("day{day_number:0>2}: part1: {part1:20} part2: {part2:20} in {duration:?}");
+        (day_number, part1, part2, start.elapsed())
+    }
+
+    // a day running longer than the timeout is cancelled, which only stops it
+    // at the next update of its progress, those which don't report any are
+    // left to finish in the background
+    fn timed_resolve_within(&'static self) -> Option<(u32, String, String, Duration)> {
+        let Some(&timeout) = TIMEOUT.get() else {
+            return Some(self.timed_resolve());
+        };
+        let (sender, receiver) = mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+
+        RUNNING.fetch_add(1, AtomicOrdering::Relaxed);
+        thread::spawn({
+            let cancel = cancel.clone();
+
+            move || {
+                let running = RunningGuard;
+
+                progress::set_cancel(cancel);
+
+                let resolved = self.timed_resolve();
+
+                // no longer running once the result is received
+                drop(running);
+                sender.send(resolved)
+            }
+        });
+
+        match receiver.recv_timeout(timeout) {
+            Ok(resolved) => Some(resolved),
+            Err(RecvTimeoutError::Timeout) => {
+                cancel.store(true, AtomicOrdering::Relaxed);
+                // time to unwind for a day that checks its progress often
+                receiver.recv_timeout(CANCEL_GRACE).ok();
+                None
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                panic!("day{:0>2} failed", self.parse_number())
+            }
+        }
+    }
+
+    // the duration is only known when the day was not cached, and only
+    // returned when no day that timed out was still running meanwhile
+    fn print(&'static self) -> Option<Duration> {
+        let day_number = self.parse_number();
+        let skewed = is_day_running();
+        let (part1, part2, duration) = match cache::load(day_number) {
+            Some((part1, part2)) => (part1, part2, None),
+            None => {
+                let Some((_, part1, part2, duration)) = self.timed_resolve_within() else {
+                    let error = format!("timed out after {:?}", TIMEOUT.get().unwrap());
+
+                    emit(
+                        format!("day{day_number:0>2}: {error}"),
+                        json!({ "day": day_number, "error": error }),
+                    );
+                    return None;
+                };
+
+                cache::store(day_number, &part1, &part2);
+                (part1, part2, Some(duration))
+            }
+        };
+        let timing = match duration {
+            Some(duration) if skewed => {
+                format!("in {duration:?}, unreliable while a day that timed out runs")
+            }
+            Some(duration) => format!("in {duration:?}"),
+            None => "cached".to_string(),
+        };
+        let expected = mismatched_answers(day_number, &part1, &part2);
+        let mut text = format!("day{day_number:0>2}: part1: {part1:20} part2: {part2:20} {timing}");
+
+        if let Some((expected1, expected2)) = &expected {
+            text += &format!(
+                "\nday{day_number:0>2}: expected part1: {expected1:11} part2: {expected2:20}"
+            );
+        }
+        emit(
+            text,
+            json!({
+                "day": day_number,
+                "part1": part1,
+                "part2": part2,
+                "duration_ms": duration.map(|d| d.as_secs_f64() * 1e3),
+                "cached": duration.is_none(),
+                "unreliable": skewed,
+                "expected": expected.map(|(part1, part2)| json!({ "part1": part1, "part2": part2 })),
+            }),
+        );
+
+        duration.filter(|_| !skewed)
     }
 
     fn parse_number(&self) -> u32 {
-        self.day_filename
-            .replace(|c: char| !c.is_ascii_digit(), "")
-            .parse::<u32>()
-            .unwrap()
+        parse_day_number(self.day_filename)
     }
 
     fn resolve(&self) -> (u32, String, String) {
         let day_number = self.parse_number();
-        let (part1, part2) =
-            (self.resolve)(read_lines(format!("./inputs/{day_number:0>2}.txt")).unwrap());
+        let _span = trace::span(format!("day{day_number:0>2}"));
+
+        progress::set_day(day_number);
+
+        let input = {
+            let _span = trace::span("read");
+
+            self.read_input(day_number, read_raw_input(day_number).unwrap())
+        };
+        let (part1, part2) = (self.resolve)(&input);
         (day_number, part1, part2)
     }
 }
 
 impl PartialEq for Day {
     fn eq(&self, other: &Self) -> bool {
-        self.day_filename == other.day_filename
+        self.day_filename == other.day_filename && self.variant == other.variant
     }
 }
 
 impl Ord for Day {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.day_filename.cmp(other.day_filename)
+        self.day_filename
+            .cmp(other.day_filename)
+            .then(self.variant.cmp(&other.variant))
     }
 }
 
@@ -62,43 +246,235 @@ impl PartialOrd for Day {
     }
 }
 
-fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
+fn parse_day_number(day_filename: &str) -> u32 {
+    day_filename
+        .replace(|c: char| !c.is_ascii_digit(), "")
+        .parse::<u32>()
+        .unwrap()
+}
+
+static INPUTS_DIR: OnceLock<PathBuf> = OnceLock::new();
+static TIMEOUT: OnceLock<Duration> = OnceLock::new();
+static OUTPUT: OnceLock<Output> = OnceLock::new();
+// days resolved in a thread of their own, still running when they timed out
+static RUNNING: AtomicUsize = AtomicUsize::new(0);
+const CANCEL_GRACE: Duration = Duration::from_millis(100);
+
+struct RunningGuard;
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        RUNNING.fetch_sub(1, AtomicOrdering::Relaxed);
+    }
+}
+
+fn is_day_running() -> bool {
+    RUNNING.load(AtomicOrdering::Relaxed) > 0
+}
+
+/// Directory of the inputs instead of `./inputs`, to be set before resolving.
+pub fn set_inputs_dir(dir: PathBuf) {
+    INPUTS_DIR.set(dir).unwrap();
+}
+
+/// Time after which `resolve` gives up on a day and goes on with the next.
+pub fn set_timeout(timeout: Duration) {
+    TIMEOUT.set(timeout).unwrap();
+}
+
+/// How `resolve` prints the answers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Output {
+    /// aligned columns
+    #[default]
+    Text,
+    /// a JSON object per line, with the fields answered by `serve`
+    Json,
+}
+
+/// Format of the answers printed by `resolve`, to be set before resolving.
+pub fn set_output(output: Output) {
+    OUTPUT.set(output).unwrap();
+}
+
+// `text` or `json` on a line of its own, depending on the output
+fn emit(text: String, json: Value) {
+    match OUTPUT.get().copied().unwrap_or_default() {
+        Output::Text => println!("{text}"),
+        Output::Json => println!("{json}"),
+    }
+}
+
+fn input_path(day_number: u32) -> String {
+    let dir = INPUTS_DIR
+        .get()
+        .map_or(Path::new("./inputs"), |d| d.as_path());
+
+    format!("{}/{day_number:0>2}.txt", dir.display())
+}
+
+mod compiled_out {
+    include!(concat!(env!("OUT_DIR"), "/compiled_out.rs"));
+}
+
+fn is_compiled_out(day_number: u32) -> bool {
+    compiled_out::COMPILED_OUT.contains(&day_number)
+}
+
+// why `day_number` can't be resolved
+fn unavailable(day_number: u32) -> String {
+    if is_compiled_out(day_number) {
+        format!("day{day_number:0>2} is compiled out, see the day{day_number:0>2} feature")
+    } else {
+        format!("day{day_number:0>2} is not implemented")
+    }
+}
+
+#[cfg(feature = "embed-inputs")]
+mod embedded {
+    include!(concat!(env!("OUT_DIR"), "/inputs.rs"));
+}
 
-    Ok(io::BufReader::new(file).lines())
+#[cfg(feature = "embed-inputs")]
+fn embedded_input(day_number: u32) -> Option<&'static str> {
+    embedded::INPUTS
+        .iter()
+        .find(|(d, _)| *d == day_number)
+        .map(|(_, input)| *input)
 }
 
-fn resolve_all() {
-    let mut days: Vec<&'static Day> = inventory::iter::<Day>.into_iter().collect();
+#[cfg(not(feature = "embed-inputs"))]
+fn embedded_input(_day_number: u32) -> Option<&'static str> {
+    None
+}
+
+// the file on disk, else the copy embedded at build time
+fn read_raw_input(day_number: u32) -> io::Result<String> {
+    match fs::read_to_string(input_path(day_number)) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            embedded_input(day_number).map(str::to_string).ok_or(e)
+        }
+        read => read,
+    }
+}
+
+// first line is part1, the rest is part2
+fn answers_path(day_number: u32) -> String {
+    format!("./answers/{day_number:0>2}.txt")
+}
+
+fn read_answers(day_number: u32) -> Option<(String, String)> {
+    let answers = fs::read_to_string(answers_path(day_number)).ok()?;
+    let (part1, part2) = answers.split_once('\n').unwrap_or((&answers, ""));
+
+    Some((part1.trim().to_string(), part2.trim().to_string()))
+}
+
+// the expected answers when they differ, surrounding whitespace aside
+fn mismatched_answers(day_number: u32, part1: &str, part2: &str) -> Option<(String, String)> {
+    read_answers(day_number)
+        .filter(|(expected1, expected2)| part1.trim() != expected1 || part2.trim() != expected2)
+}
+
+fn all_days() -> Vec<&'static Day> {
+    let mut days: Vec<&'static Day> = inventory::iter::<Day>
+        .into_iter()
+        .filter(|d| d.variant.is_none())
+        .collect();
 
     days.sort_unstable();
+    days
+}
 
-    days.iter().for_each(|d| d.print());
+fn resolve_all() -> Vec<(u32, Duration)> {
+    let timings = all_days()
+        .iter()
+        .filter_map(|d| Some((d.parse_number(), d.print()?)))
+        .collect();
+    let compiled_out = compiled_out::COMPILED_OUT
+        .iter()
+        .map(|d| format!("day{d:0>2}"))
+        .collect::<Vec<_>>();
+
+    if !compiled_out.is_empty() {
+        emit(
+            format!("compiled out: {}", compiled_out.join(" ")),
+            json!({ "compiled_out": compiled_out::COMPILED_OUT }),
+        );
+    }
+    timings
 }
 
-fn resolve_one(day_number: u32) {
-    inventory::iter::<Day>
+fn resolve_one(day_number: u32) -> Option<Duration> {
+    let Some(day) = inventory::iter::<Day>
         .into_iter()
-        .find(|d| d.parse_number() == day_number)
-        .unwrap()
-        .print();
+        .find(|d| d.variant.is_none() && d.parse_number() == day_number)
+    else {
+        emit(
+            unavailable(day_number),
+            json!({ "day": day_number, "error": unavailable(day_number) }),
+        );
+        return None;
+    };
+
+    day.print()
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+/// Resolves `day_number` on `input` instead of its input file, the error
+/// being the reason the day panicked.
+pub fn solve(day_number: u32, input: String) -> Result<(String, String, Duration), String> {
+    let day = inventory::iter::<Day>
+        .into_iter()
+        .find(|d| d.variant.is_none() && d.parse_number() == day_number)
+        .ok_or_else(|| unavailable(day_number))?;
+    let input = day.read_input(day_number, input);
+
+    panic::catch_unwind(AssertUnwindSafe(|| {
+        let _span = trace::span(format!("day{day_number:0>2}"));
+        let start = Instant::now();
+
+        progress::set_day(day_number);
+
+        let (part1, part2) = (day.resolve)(&input);
+
+        (part1, part2, start.elapsed())
+    }))
+    .map_err(|payload| panic_message(&*payload))
+}
+
+/// Numbers of the registered days.
+pub fn days() -> Vec<u32> {
+    all_days().iter().map(|d| d.parse_number()).collect()
 }
 
 pub fn resolve(days: &[u32]) {
     let start = Instant::now();
 
-    if days.is_empty() {
-        resolve_all();
+    let timings = if days.is_empty() {
+        resolve_all()
     } else {
-        days.iter().for_each(|&d| resolve_one(d));
-    }
+        days.iter()
+            .filter_map(|&d| Some((d, resolve_one(d)?)))
+            .collect()
+    };
 
     let duration = start.elapsed();
 
-    println!("All done in {duration:?}");
+    history::record(&timings);
+
+    emit(
+        format!("All done in {duration:?}"),
+        json!({ "duration_ms": duration.as_secs_f64() * 1e3 }),
+    );
 }
 
 inventory::collect!(Day);
@@ -0,0 +1,124 @@
+use crate::generate::Generator;
+use crate::simulation::Simulator;
+use crate::{answers_path, embedded_input, history, input_path, is_compiled_out, params, Day};
+use std::path::Path;
+
+// the puzzles of the year
+const LAST_DAY: u32 = 25;
+
+fn registered(day_number: u32) -> Vec<&'static Day> {
+    let mut days = inventory::iter::<Day>
+        .into_iter()
+        .filter(|d| d.parse_number() == day_number)
+        .collect::<Vec<_>>();
+
+    days.sort_unstable();
+    days
+}
+
+fn input_source(day_number: u32) -> &'static str {
+    if Path::new(&input_path(day_number)).exists() {
+        "yes"
+    } else if embedded_input(day_number).is_some() {
+        "embedded"
+    } else {
+        "no"
+    }
+}
+
+fn answers_source(day_number: u32) -> &'static str {
+    if Path::new(&answers_path(day_number)).exists() {
+        "yes"
+    } else {
+        "no"
+    }
+}
+
+fn missing(day_number: u32) -> &'static str {
+    if is_compiled_out(day_number) {
+        "compiled out"
+    } else {
+        "not implemented"
+    }
+}
+
+/// Prints a line per puzzle telling what is there to resolve it.
+pub fn list() {
+    let timings = history::last_timings();
+
+    println!(
+        "{:5} {:26} {:5} {:8} {:7} last run",
+        "day", "title", "parts", "input", "answers"
+    );
+
+    for day_number in 1..=LAST_DAY {
+        let Some(day) = registered(day_number)
+            .into_iter()
+            .find(|d| d.variant.is_none())
+        else {
+            println!("day{day_number:0>2} {}", missing(day_number));
+            continue;
+        };
+        let last_run = timings
+            .get(&day_number)
+            .map_or("never".to_string(), |d| format!("{d:?}"));
+
+        println!(
+            "day{day_number:0>2} {:26} {:<5} {:8} {:7} {last_run}",
+            day.title,
+            day.parts,
+            input_source(day_number),
+            answers_source(day_number)
+        );
+    }
+}
+
+/// Prints everything known about `day_number`.
+pub fn info(day_number: u32) {
+    let days = registered(day_number);
+    let Some(day) = days.iter().find(|d| d.variant.is_none()) else {
+        println!("day{day_number:0>2}: {}", missing(day_number));
+        return;
+    };
+    let yes_no = |b: bool| if b { "yes" } else { "no" };
+
+    println!("day{day_number:0>2}: {}", day.title);
+    println!("parts: {}", day.parts);
+    println!(
+        "variants: {}",
+        days.iter().map(|d| d.name()).collect::<Vec<_>>().join(", ")
+    );
+    println!(
+        "input: {} {}",
+        input_path(day_number),
+        input_source(day_number)
+    );
+    println!(
+        "answers: {} {}",
+        answers_path(day_number),
+        answers_source(day_number)
+    );
+    println!(
+        "simulation: {}",
+        yes_no(
+            inventory::iter::<Simulator>
+                .into_iter()
+                .any(|s| s.parse_number() == day_number)
+        )
+    );
+    println!(
+        "generator: {}",
+        yes_no(
+            inventory::iter::<Generator>
+                .into_iter()
+                .any(|g| g.parse_number() == day_number)
+        )
+    );
+    println!(
+        "last run: {}",
+        history::last_timings()
+            .get(&day_number)
+            .map_or("never".to_string(), |d| format!("{d:?}"))
+    );
+    params::list(&[day_number]);
+}
@@ -1,24 +1,269 @@
-use clap::Parser;
-mod days;
+use advent_2022::config::Config;
+use advent_2022::Output;
+use advent_2022::{cache, history, params, progress, trace};
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+use std::process;
+use std::time::Duration;
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    #[arg(short, long, help = "limit the number of threads used by rayon")]
+    #[arg(
+        short,
+        long,
+        global = true,
+        help = "limit the number of threads used by rayon"
+    )]
     nthreads: Option<usize>,
+    #[arg(
+        long,
+        global = true,
+        help = "directory of the inputs [default: ./inputs]"
+    )]
+    inputs: Option<PathBuf>,
+    #[arg(
+        long,
+        global = true,
+        value_name = "SECONDS",
+        help = "give up on the days running longer"
+    )]
+    timeout: Option<u64>,
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        help = "format of the answers [default: text]"
+    )]
+    output: Option<Output>,
+    #[arg(long, global = true, help = "record the spans of the run into a file")]
+    trace: Option<PathBuf>,
+    #[arg(
+        long,
+        global = true,
+        conflicts_with = "trace",
+        help = "don't record the spans, whatever advent.toml says"
+    )]
+    no_trace: bool,
+    #[arg(long, global = true, value_enum, help = "[default: chrome]")]
+    trace_format: Option<trace::Format>,
+    #[arg(
+        long,
+        global = true,
+        help = "reuse the answers of a previous run on the same input"
+    )]
+    cache: bool,
+    #[arg(
+        long,
+        global = true,
+        requires = "cache",
+        help = "resolve again and overwrite the cached answers"
+    )]
+    refresh: bool,
+    #[arg(
+        long,
+        global = true,
+        overrides_with = "no_record",
+        help = "append the timings to ./history.txt"
+    )]
+    record: bool,
+    #[arg(
+        long,
+        global = true,
+        overrides_with = "record",
+        help = "don't append the timings, whatever advent.toml says"
+    )]
+    no_record: bool,
+    #[arg(
+        long = "param",
+        global = true,
+        value_name = "[DAY.]NAME=VALUE",
+        value_parser = params::parse_override,
+        help = "change a puzzle constant of the days, see the params command"
+    )]
+    params: Vec<params::Override>,
+    #[command(subcommand)]
+    command: Option<Command>,
     #[arg(trailing_var_arg = true)]
     days: Vec<u32>,
 }
 
+#[derive(Subcommand)]
+enum Command {
+    #[command(about = "step interactively through a simulation day")]
+    Step { day: u32 },
+    #[command(about = "resolve days again each time their input or answers change")]
+    Watch { days: Vec<u32> },
+    #[command(about = "run every registered variant of days and compare them")]
+    Compare { days: Vec<u32> },
+    #[command(about = "generate a random input for a day")]
+    Generate {
+        day: u32,
+        #[arg(
+            short,
+            long,
+            default_value_t = 0,
+            help = "seed of the random generator"
+        )]
+        seed: u64,
+        #[arg(long, help = "day specific size of the input")]
+        size: Option<usize>,
+        #[arg(short, long, help = "write into the inputs directory")]
+        write: bool,
+        #[arg(short, long, requires = "write", help = "overwrite an existing input")]
+        force: bool,
+    },
+    #[command(about = "compare the last recorded timings with the previous ones")]
+    Report {
+        days: Vec<u32>,
+        #[arg(
+            short,
+            long,
+            help = "compare with the timings of this commit, as recorded"
+        )]
+        baseline: Option<String>,
+        #[arg(
+            short,
+            long,
+            default_value_t = 5,
+            help = "number of previous timings of the rolling median"
+        )]
+        window: usize,
+        #[arg(
+            short,
+            long,
+            default_value_t = 1.25,
+            help = "ratio above which a day is flagged as slower"
+        )]
+        threshold: f64,
+    },
+    #[command(about = "list the puzzles with what is there to resolve them")]
+    List,
+    #[command(about = "describe a day")]
+    Info { day: u32 },
+    #[command(about = "list the parameters of days with their defaults")]
+    Params { days: Vec<u32> },
+    #[command(about = "time days in pools of 1, 2, 4... threads")]
+    Scaling {
+        days: Vec<u32>,
+        #[arg(short, long, help = "largest pool [default: available parallelism]")]
+        max_threads: Option<usize>,
+        #[arg(
+            short,
+            long,
+            default_value_t = 3,
+            help = "runs per pool, the fastest one being kept"
+        )]
+        runs: usize,
+    },
+    #[command(about = "answer the inputs posted to /day/{n} with JSON")]
+    Serve {
+        #[arg(short, long, default_value = "127.0.0.1:8022")]
+        address: String,
+    },
+    #[command(about = "remove every cached answer")]
+    ClearCache,
+}
+
 fn main() {
     let args = Args::parse();
+    // the command line takes precedence over the configuration file
+    let config = Config::discover().unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(2);
+    });
+    // days of the commands resolving several when none is given
+    let days = |days: Vec<u32>| {
+        if days.is_empty() {
+            config.days.clone()
+        } else {
+            days
+        }
+    };
+    let mut overrides = config.overrides().unwrap_or_else(|e| {
+        eprintln!("advent.toml: {e}");
+        process::exit(2);
+    });
+    let trace = if args.no_trace {
+        None
+    } else {
+        args.trace.or(config.trace)
+    };
+    let trace_format = args
+        .trace_format
+        .or(config.trace_format)
+        .unwrap_or(trace::Format::Chrome);
 
-    if let Some(nthreads) = args.nthreads {
+    if let Some(nthreads) = args.nthreads.or(config.nthreads) {
         rayon::ThreadPoolBuilder::new()
             .num_threads(nthreads)
             .build_global()
             .unwrap();
     }
 
-    advent_2022::resolve(&args.days);
+    if let Some(inputs) = args.inputs.or(config.inputs) {
+        advent_2022::set_inputs_dir(inputs);
+    }
+
+    if let Some(timeout) = args.timeout.or(config.timeout) {
+        advent_2022::set_timeout(Duration::from_secs(timeout));
+    }
+
+    if let Some(output) = args.output.or(config.output) {
+        advent_2022::set_output(output);
+    }
+
+    overrides.extend(args.params);
+    params::set(overrides);
+
+    if args.cache {
+        cache::enable(args.refresh);
+    }
+
+    if args.record || (config.record && !args.no_record) {
+        history::enable();
+    }
+
+    progress::enable();
+
+    if trace.is_some() {
+        trace::enable();
+    }
+
+    match args.command {
+        Some(Command::Step { day }) => advent_2022::simulation::step(day),
+        Some(Command::Watch { days: d }) => advent_2022::watch::watch(&days(d)),
+        Some(Command::Compare { days: d }) => advent_2022::compare::compare(&days(d)),
+        Some(Command::Generate {
+            day,
+            seed,
+            size,
+            write,
+            force,
+        }) => advent_2022::generate::generate(day, seed, size, write, force),
+        Some(Command::Report {
+            days,
+            baseline,
+            window,
+            threshold,
+        }) => history::report(&days, baseline.as_deref(), window, threshold),
+        Some(Command::List) => advent_2022::list::list(),
+        Some(Command::Info { day }) => advent_2022::list::info(day),
+        Some(Command::Params { days }) => params::list(&days),
+        Some(Command::Scaling {
+            days: d,
+            max_threads,
+            runs,
+        }) => advent_2022::scaling::scaling(&days(d), max_threads, runs),
+        Some(Command::Serve { address }) => advent_2022::serve::serve(&address),
+        Some(Command::ClearCache) => match cache::clear() {
+            Ok(count) => println!("removed {count} cached answers"),
+            Err(e) => eprintln!("cannot clear the cache: {e}"),
+        },
+        None => advent_2022::resolve(&days(args.days)),
+    }
+
+    if let Some(path) = trace {
+        trace::write(path, trace_format).unwrap();
+    }
 }
@@ -0,0 +1,66 @@
+/// Removes the byte order mark, converts the line endings to `\n` and ends
+/// the input with a single newline, whatever the machine it was saved on.
+pub fn normalize(raw: String) -> String {
+    let without_bom = raw.strip_prefix('\u{feff}').unwrap_or(&raw);
+    let mut input = if without_bom.contains('\r') {
+        without_bom.replace("\r\n", "\n").replace('\r', "\n")
+    } else if without_bom.len() < raw.len() {
+        without_bom.to_string()
+    } else {
+        raw
+    };
+    let length = input.trim_end_matches('\n').len();
+
+    input.truncate(length);
+    if !input.is_empty() {
+        input.push('\n');
+    }
+    input
+}
+
+/// What looks wrong in a normalized input. Trailing whitespace is expected
+/// when the lines are `padded`.
+pub fn warnings(input: &str, padded: bool) -> Vec<String> {
+    if input.trim().is_empty() {
+        return vec!["empty input".to_string()];
+    }
+
+    let mut warnings = vec![];
+    let trailing = input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| line.ends_with(char::is_whitespace))
+        .map(|(i, _)| i + 1)
+        .collect::<Vec<_>>();
+
+    if let (false, Some(first)) = (padded, trailing.first()) {
+        warnings.push(format!(
+            "trailing whitespace on {} lines, the first being line {first}",
+            trailing.len()
+        ));
+    }
+    if let Some(i) = input.lines().position(|line| !line.is_ascii()) {
+        warnings.push(format!("non-ASCII character on line {}", i + 1));
+    }
+    warnings
+}
+
+#[test]
+fn check_normalize() {
+    assert_eq!(normalize("\u{feff}1\r\n2\r\n\r\n".to_string()), "1\n2\n");
+    assert_eq!(normalize("1\r2".to_string()), "1\n2\n");
+    assert_eq!(
+        normalize("    [D]    \n\n1".to_string()),
+        "    [D]    \n\n1\n"
+    );
+    assert_eq!(normalize("\n\n".to_string()), "");
+
+    assert_eq!(warnings("", false), vec!["empty input"]);
+    assert!(warnings("1\n2\n", false).is_empty());
+    assert_eq!(warnings("1 \n2\n3\t\n", false).len(), 1);
+    assert!(warnings("1 \n2\n", true).is_empty());
+    assert_eq!(
+        warnings("1\n2\u{a0}3\n", false),
+        vec!["non-ASCII character on line 2"]
+    );
+}
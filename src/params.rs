@@ -0,0 +1,210 @@
+use crate::{all_days, parse_day_number};
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+/// A puzzle constant of a day that can be changed from the command line.
+pub struct Param {
+    day_filename: &'static str,
+    name: &'static str,
+    default: &'static str,
+    help: &'static str,
+    min: Option<i64>,
+}
+
+impl Param {
+    pub const fn new(
+        day_filename: &'static str,
+        name: &'static str,
+        default: &'static str,
+        help: &'static str,
+    ) -> Self {
+        Param {
+            day_filename,
+            name,
+            default,
+            help,
+            min: None,
+        }
+    }
+
+    /// Smallest value of an integer parameter, below which the day can't run.
+    pub const fn at_least(self, min: i64) -> Self {
+        Param {
+            min: Some(min),
+            ..self
+        }
+    }
+
+    fn validate(&self, value: &str) -> Result<(), String> {
+        let Some(min) = self.min else {
+            return Ok(());
+        };
+
+        match value.parse::<i64>() {
+            Ok(value) if value >= min => Ok(()),
+            _ => Err(format!(
+                "day{:0>2}: {} must be an integer of at least {min}, got {value}",
+                self.parse_number(),
+                self.name
+            )),
+        }
+    }
+
+    fn parse_number(&self) -> u32 {
+        parse_day_number(self.day_filename)
+    }
+}
+
+inventory::collect!(Param);
+
+/// `[DAY.]NAME=VALUE` given on the command line, for every day declaring
+/// `NAME` when `DAY` is missing.
+#[derive(Clone, Debug)]
+pub struct Override {
+    day_number: Option<u32>,
+    name: String,
+    value: String,
+}
+
+impl Override {
+    fn applies_to(&self, param: &Param) -> bool {
+        self.name == param.name && self.day_number.is_none_or(|d| d == param.parse_number())
+    }
+}
+
+static OVERRIDES: Mutex<Vec<Override>> = Mutex::new(vec![]);
+
+/// Parses an override, rejecting the parameters no day declares and the
+/// values out of their range.
+pub fn parse_override(arg: &str) -> Result<Override, String> {
+    let (key, value) = arg
+        .split_once('=')
+        .ok_or_else(|| format!("expected [DAY.]NAME=VALUE, got {arg}"))?;
+    let (day_number, name) = match key.split_once('.') {
+        Some((day, name)) => (
+            Some(
+                day.parse::<u32>()
+                    .map_err(|_| format!("invalid day {day}"))?,
+            ),
+            name,
+        ),
+        None => (None, key),
+    };
+    let parameter = Override {
+        day_number,
+        name: name.to_string(),
+        value: value.to_string(),
+    };
+
+    let params = inventory::iter::<Param>
+        .into_iter()
+        .filter(|p| parameter.applies_to(p))
+        .collect::<Vec<_>>();
+
+    if params.is_empty() {
+        return Err(format!(
+            "no day has a parameter {key}, see the params command"
+        ));
+    }
+    for param in params {
+        param.validate(value)?;
+    }
+    Ok(parameter)
+}
+
+/// Overrides used by `get` from now on, the last one winning.
+pub fn set(overrides: Vec<Override>) {
+    *OVERRIDES.lock().unwrap() = overrides;
+}
+
+fn override_of(overrides: &[Override], param: &Param) -> Option<String> {
+    overrides
+        .iter()
+        .rev()
+        .find(|o| o.applies_to(param))
+        .map(|o| o.value.clone())
+}
+
+/// Value of the parameter `name` declared by `day_filename`.
+pub fn get<T>(day_filename: &str, name: &str) -> T
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let param = inventory::iter::<Param>
+        .into_iter()
+        .find(|p| p.day_filename == day_filename && p.name == name)
+        .unwrap_or_else(|| panic!("{day_filename} doesn't declare the parameter {name}"));
+    let value =
+        override_of(&OVERRIDES.lock().unwrap(), param).unwrap_or_else(|| param.default.to_string());
+
+    value.parse().unwrap_or_else(|e| {
+        panic!(
+            "day{:0>2}: invalid {name} {value}: {e}",
+            param.parse_number()
+        )
+    })
+}
+
+/// Names and values of the overridden parameters of `day_number`, sorted by
+/// name, empty when the day runs with its defaults.
+pub fn overridden(day_number: u32) -> Vec<(&'static str, String)> {
+    let overrides = OVERRIDES.lock().unwrap();
+    let mut values = inventory::iter::<Param>
+        .into_iter()
+        .filter(|p| p.parse_number() == day_number)
+        .filter_map(|p| Some((p.name, override_of(&overrides, p)?)))
+        .collect::<Vec<_>>();
+
+    values.sort_unstable();
+    values
+}
+
+/// Prints the parameters of `days` (all days if empty) with their defaults.
+pub fn list(days: &[u32]) {
+    let days = if days.is_empty() {
+        all_days().iter().map(|d| d.parse_number()).collect()
+    } else {
+        days.to_vec()
+    };
+
+    for day_number in days {
+        let mut params = inventory::iter::<Param>
+            .into_iter()
+            .filter(|p| p.parse_number() == day_number)
+            .collect::<Vec<_>>();
+
+        params.sort_unstable_by_key(|p| p.name);
+
+        for p in params {
+            println!(
+                "day{day_number:0>2}: {:12} {:12} {}",
+                p.name, p.default, p.help
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+inventory::submit! { Param::new("src/days/day99.rs", "check", "1", "only used by the tests").at_least(1) }
+
+#[test]
+fn check_override() {
+    assert!(parse_override("check").is_err());
+    assert!(parse_override("x.check=2").is_err());
+    assert!(parse_override("unknown=2").is_err());
+    assert!(parse_override("98.check=2").is_err());
+    assert!(parse_override("check=0").is_err());
+    assert!(parse_override("99.check=x").is_err());
+    assert_eq!(get::<u32>("src/days/day99.rs", "check"), 1);
+    assert!(overridden(99).is_empty());
+
+    set(vec![
+        parse_override("check=2").unwrap(),
+        parse_override("99.check=3").unwrap(),
+    ]);
+    assert_eq!(get::<u32>("src/days/day99.rs", "check"), 3);
+    assert_eq!(overridden(99), [("check", "3".to_string())]);
+    set(vec![]);
+}
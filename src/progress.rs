@@ -0,0 +1,176 @@
+use std::cell::RefCell;
+use std::io::{self, IsTerminal, Write};
+use std::panic;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+const RENDER_INTERVAL: Duration = Duration::from_millis(100);
+// updates counted by a batch before they are added to its progress
+const BATCH_SIZE: u64 = 4096;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static CURRENT_DAY: AtomicU32 = AtomicU32::new(0);
+static ORIGIN: OnceLock<Instant> = OnceLock::new();
+
+/// Renders the progress of the days on stderr, when it is a terminal.
+pub fn enable() {
+    if io::stderr().is_terminal() {
+        ORIGIN.get_or_init(Instant::now);
+        ENABLED.store(true, Ordering::Relaxed);
+    }
+}
+
+fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+pub(crate) fn set_day(day_number: u32) {
+    CURRENT_DAY.store(day_number, Ordering::Relaxed);
+}
+
+thread_local! {
+    // flag of the day resolved by this thread, raised once it timed out
+    static CANCEL: RefCell<Option<Arc<AtomicBool>>> = const { RefCell::new(None) };
+}
+
+// payload of the unwinding of a cancelled day
+struct Cancelled;
+
+/// The progress created from now on by this thread stops its day at the
+/// next update once `cancel` is raised.
+pub(crate) fn set_cancel(cancel: Arc<AtomicBool>) {
+    CANCEL.with(|c| *c.borrow_mut() = Some(cancel));
+}
+
+fn since_origin() -> u64 {
+    ORIGIN.get().unwrap().elapsed().as_nanos() as u64
+}
+
+/// Count of work done by a solver out of an optional total, rendered as a
+/// line updated in place until dropped. Updates are shared by the threads of
+/// a parallel iterator and cost an atomic addition when not rendered, which
+/// the threads contend for: hot loops count through a `batch` of their own.
+/// Updates are also where a day that timed out is stopped.
+pub struct Progress {
+    name: &'static str,
+    total: Option<u64>,
+    cancel: Option<Arc<AtomicBool>>,
+    done: AtomicU64,
+    // creation at first, fast solvers never show a line
+    rendered_at: AtomicU64,
+    shown: AtomicBool,
+}
+
+impl Progress {
+    pub fn new(name: &'static str, total: Option<u64>) -> Self {
+        Progress {
+            name,
+            total,
+            cancel: CANCEL.with(|c| c.borrow().clone()),
+            done: AtomicU64::new(0),
+            rendered_at: AtomicU64::new(if is_enabled() { since_origin() } else { 0 }),
+            shown: AtomicBool::new(false),
+        }
+    }
+
+    pub fn add(&self, count: u64) {
+        self.check_cancelled();
+
+        let done = self.done.fetch_add(count, Ordering::Relaxed) + count;
+
+        self.render(done);
+    }
+
+    /// Counter of a single thread adding its updates to this progress by
+    /// batches, for the hottest loops.
+    pub fn batch(&self) -> Batch<'_> {
+        Batch {
+            progress: self,
+            count: 0,
+        }
+    }
+
+    pub fn set(&self, done: u64) {
+        self.check_cancelled();
+        self.done.store(done, Ordering::Relaxed);
+        self.render(done);
+    }
+
+    // unwinds without the message of a panic, through the threads of a
+    // parallel iterator too
+    fn check_cancelled(&self) {
+        if self
+            .cancel
+            .as_ref()
+            .is_some_and(|c| c.load(Ordering::Relaxed))
+        {
+            panic::resume_unwind(Box::new(Cancelled));
+        }
+    }
+
+    fn render(&self, done: u64) {
+        if !is_enabled() {
+            return;
+        }
+
+        let now = since_origin();
+        let rendered_at = self.rendered_at.load(Ordering::Relaxed);
+
+        if now.saturating_sub(rendered_at) < RENDER_INTERVAL.as_nanos() as u64
+            || self
+                .rendered_at
+                .compare_exchange(rendered_at, now, Ordering::Relaxed, Ordering::Relaxed)
+                .is_err()
+        {
+            return;
+        }
+        self.shown.store(true, Ordering::Relaxed);
+
+        let count = match self.total {
+            Some(total) => format!(
+                "{done}/{total} {:.0}%",
+                100. * done as f64 / total.max(1) as f64
+            ),
+            None => done.to_string(),
+        };
+
+        eprint!(
+            "\r\x1b[Kday{:0>2} {}: {count}",
+            CURRENT_DAY.load(Ordering::Relaxed),
+            self.name
+        );
+        io::stderr().flush().ok();
+    }
+}
+
+pub struct Batch<'a> {
+    progress: &'a Progress,
+    count: u64,
+}
+
+impl Batch<'_> {
+    pub fn add(&mut self, count: u64) {
+        self.count += count;
+
+        if self.count >= BATCH_SIZE {
+            self.progress.add(self.count);
+            self.count = 0;
+        }
+    }
+}
+
+impl Drop for Batch<'_> {
+    // without checking for a cancellation, the day may be unwinding already
+    fn drop(&mut self) {
+        self.progress.done.fetch_add(self.count, Ordering::Relaxed);
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        if *self.shown.get_mut() {
+            eprint!("\r\x1b[K");
+        }
+    }
+}
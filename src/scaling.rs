@@ -0,0 +1,124 @@
+use crate::{all_days, unavailable, Day};
+use std::thread;
+use std::time::Duration;
+
+// 1, 2, 4... and `max` itself
+fn thread_counts(max: usize) -> Vec<usize> {
+    let mut counts = (0..)
+        .map(|i| 1 << i)
+        .take_while(|&c| c < max)
+        .collect::<Vec<_>>();
+
+    counts.push(max);
+    counts
+}
+
+// best of `runs` in a pool of `threads`, with the answers
+fn timed_in_pool(day: &Day, threads: usize, runs: usize) -> (Duration, (String, String)) {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .unwrap();
+
+    (0..runs.max(1))
+        .map(|_| {
+            let (_, part1, part2, duration) = pool.install(|| day.timed_resolve());
+
+            (duration, (part1, part2))
+        })
+        .min_by_key(|(duration, _)| *duration)
+        .unwrap()
+}
+
+fn print_table(title: &str, counts: &[usize], rows: &[(u32, Vec<String>)]) {
+    print!("{title:10}");
+    counts.iter().for_each(|c| print!(" {c:>12}"));
+    println!();
+
+    for (day_number, cells) in rows {
+        print!("day{day_number:0>2}     ");
+        cells.iter().for_each(|c| print!(" {c:>12}"));
+        println!();
+    }
+    println!();
+}
+
+/// Runs `days` (all days if empty) in local pools of 1, 2, 4... up to
+/// `max_threads` threads and prints the time, speedup and parallel efficiency
+/// of each pool size.
+pub fn scaling(days: &[u32], max_threads: Option<usize>, runs: usize) {
+    let max_threads = max_threads
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get()))
+        .max(1);
+    let counts = thread_counts(max_threads);
+    let days = if days.is_empty() {
+        all_days()
+    } else {
+        days.iter()
+            .filter_map(|&day_number| {
+                let day = all_days()
+                    .into_iter()
+                    .find(|d| d.parse_number() == day_number);
+
+                if day.is_none() {
+                    println!("{}", unavailable(day_number));
+                }
+                day
+            })
+            .collect()
+    };
+    let mut times = vec![];
+
+    for day in days {
+        let timings = counts
+            .iter()
+            .map(|&threads| timed_in_pool(day, threads, runs))
+            .collect::<Vec<_>>();
+
+        if timings.iter().any(|(_, answers)| *answers != timings[0].1) {
+            println!(
+                "day{:0>2}: answers change with the number of threads",
+                day.parse_number()
+            );
+        }
+        times.push((
+            day.parse_number(),
+            timings.into_iter().map(|(d, _)| d).collect::<Vec<_>>(),
+        ));
+    }
+
+    let table = |cell: &dyn Fn(usize, Duration, Duration) -> String| {
+        times
+            .iter()
+            .map(|(day_number, durations)| {
+                let cells = counts
+                    .iter()
+                    .zip(durations)
+                    .map(|(&threads, &duration)| cell(threads, durations[0], duration))
+                    .collect();
+
+                (*day_number, cells)
+            })
+            .collect::<Vec<_>>()
+    };
+    let speedup = |one: Duration, duration: Duration| one.as_secs_f64() / duration.as_secs_f64();
+
+    print_table("time", &counts, &table(&|_, _, d| format!("{d:.2?}")));
+    print_table(
+        "speedup",
+        &counts,
+        &table(&|_, one, d| format!("{:.2}x", speedup(one, d))),
+    );
+    print_table(
+        "efficiency",
+        &counts,
+        &table(&|threads, one, d| format!("{:.0}%", 100. * speedup(one, d) / threads as f64)),
+    );
+}
+
+#[test]
+fn check_thread_counts() {
+    assert_eq!(thread_counts(1), vec![1]);
+    assert_eq!(thread_counts(4), vec![1, 2, 4]);
+    assert_eq!(thread_counts(6), vec![1, 2, 4, 6]);
+}
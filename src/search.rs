@@ -0,0 +1,244 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+fn build_path<N>(nodes: &[N], parents: &[usize], mut index: usize) -> Vec<N>
+where
+    N: Clone,
+{
+    let mut path = vec![nodes[index].clone()];
+
+    while parents[index] != index {
+        index = parents[index];
+        path.push(nodes[index].clone());
+    }
+
+    path.reverse();
+    path
+}
+
+/// Breadth first search from `start` until `success` matches a node.
+/// Returns the path including both `start` and the found node.
+pub fn bfs<N, FN, IN, FS>(start: N, mut neighbours: FN, mut success: FS) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FS: FnMut(&N) -> bool,
+{
+    let mut nodes = vec![start.clone()];
+    let mut parents = vec![0];
+    let mut seen = HashMap::from([(start, 0)]);
+    let mut queue = VecDeque::from([0]);
+
+    while let Some(index) = queue.pop_front() {
+        if success(&nodes[index]) {
+            return Some(build_path(&nodes, &parents, index));
+        }
+
+        for n in neighbours(&nodes[index]) {
+            if seen.contains_key(&n) {
+                continue;
+            }
+
+            let new_index = nodes.len();
+
+            seen.insert(n.clone(), new_index);
+            nodes.push(n);
+            parents.push(index);
+            queue.push_back(new_index);
+        }
+    }
+    None
+}
+
+/// A* search from `start` until `success` matches a node. `neighbours`
+/// yields `(node, cost)` pairs and `heuristic` must never overestimate the
+/// remaining cost. Returns the path and its total cost.
+pub fn astar<N, C, FN, IN, FH, FS>(
+    start: N,
+    mut neighbours: FN,
+    mut heuristic: FH,
+    mut success: FS,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FS: FnMut(&N) -> bool,
+{
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+    let mut nodes = vec![start.clone()];
+    let mut parents = vec![0];
+    let mut costs = vec![C::default()];
+    let mut seen = HashMap::from([(start, 0)]);
+
+    while let Some(Reverse((_, cost, index))) = heap.pop() {
+        if cost > costs[index] {
+            // already reached with a lower cost
+            continue;
+        }
+
+        if success(&nodes[index]) {
+            return Some((build_path(&nodes, &parents, index), cost));
+        }
+
+        for (n, c) in neighbours(&nodes[index]) {
+            let new_cost = cost + c;
+            let new_index = match seen.get(&n) {
+                Some(&i) if costs[i] <= new_cost => continue,
+                Some(&i) => {
+                    costs[i] = new_cost;
+                    parents[i] = index;
+                    i
+                }
+                None => {
+                    let i = nodes.len();
+
+                    seen.insert(n.clone(), i);
+                    nodes.push(n);
+                    parents.push(index);
+                    costs.push(new_cost);
+                    i
+                }
+            };
+
+            heap.push(Reverse((
+                new_cost + heuristic(&nodes[new_index]),
+                new_cost,
+                new_index,
+            )));
+        }
+    }
+    None
+}
+
+/// Dijkstra search from `start` until `success` matches a node, this is
+/// `astar` without heuristic.
+pub fn dijkstra<N, C, FN, IN, FS>(start: N, neighbours: FN, success: FS) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FS: FnMut(&N) -> bool,
+{
+    astar(start, neighbours, |_| C::default(), success)
+}
+
+/// All pairs shortest paths (Floyd-Warshall) on nodes `0..count`.
+/// `distances[i][j]` is `None` when `j` can't be reached from `i`.
+pub fn floyd_warshall<C, FN, IN>(count: usize, mut neighbours: FN) -> Vec<Vec<Option<C>>>
+where
+    C: Copy + Ord + Default + Add<Output = C>,
+    FN: FnMut(usize) -> IN,
+    IN: IntoIterator<Item = (usize, C)>,
+{
+    let mut distances = vec![vec![None; count]; count];
+
+    for (i, row) in distances.iter_mut().enumerate() {
+        for (j, c) in neighbours(i) {
+            if row[j].is_none_or(|d| c < d) {
+                row[j] = Some(c);
+            }
+        }
+        row[i] = Some(C::default());
+    }
+
+    for k in 0..count {
+        let row_k = distances[k].clone();
+
+        for row in distances.iter_mut() {
+            let Some(ik) = row[k] else {
+                continue;
+            };
+
+            for (ij, kj) in row.iter_mut().zip(&row_k) {
+                if let Some(kj) = kj {
+                    let d = ik + *kj;
+
+                    if ij.is_none_or(|ij| d < ij) {
+                        *ij = Some(d);
+                    }
+                }
+            }
+        }
+    }
+
+    distances
+}
+
+#[cfg(test)]
+const GRAPH: [&[(usize, u32)]; 6] = [
+    &[(1, 7), (2, 9), (5, 14)],
+    &[(0, 7), (2, 10), (3, 15)],
+    &[(0, 9), (1, 10), (3, 11), (5, 2)],
+    &[(1, 15), (2, 11), (4, 6)],
+    &[(3, 6), (5, 9)],
+    &[(0, 14), (2, 2), (4, 9)],
+];
+
+#[test]
+fn check_bfs() {
+    let path = bfs(0, |&n| GRAPH[n].iter().map(|&(j, _)| j), |&n| n == 4).unwrap();
+
+    assert_eq!(path.len(), 3);
+    assert_eq!(path[0], 0);
+    assert_eq!(path[2], 4);
+
+    let path = bfs(
+        (0i32, 0i32),
+        |&(x, y)| [(x + 1, y), (x, y + 1)],
+        |&p| p == (2, 3),
+    )
+    .unwrap();
+
+    assert_eq!(path.len(), 6);
+    assert_eq!(bfs(0, |_| [], |&n| n == 1), None);
+}
+
+#[test]
+fn check_dijkstra() {
+    let (path, cost) = dijkstra(0, |&n| GRAPH[n].iter().copied(), |&n| n == 4).unwrap();
+
+    assert_eq!(path, [0, 2, 5, 4]);
+    assert_eq!(cost, 20);
+}
+
+#[test]
+fn check_astar() {
+    let goal = (5i32, 7i32);
+    let (path, cost) = astar(
+        (0i32, 0i32),
+        |&(x, y)| {
+            [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                .into_iter()
+                .filter(|&(x, y)| x != 3 || y > 5)
+                .map(|p| (p, 1u32))
+        },
+        |&(x, y)| x.abs_diff(goal.0) + y.abs_diff(goal.1),
+        |&p| p == goal,
+    )
+    .unwrap();
+
+    assert_eq!(cost, 12);
+    assert_eq!(path.len(), 13);
+    assert_eq!(path.last(), Some(&goal));
+}
+
+#[test]
+fn check_floyd_warshall() {
+    let distances = floyd_warshall(6, |n| GRAPH[n].iter().copied());
+
+    assert_eq!(distances[0][4], Some(20));
+    assert_eq!(distances[4][0], Some(20));
+    assert_eq!(distances[1][5], Some(12));
+    assert_eq!(distances[3][3], Some(0));
+
+    let distances = floyd_warshall(2, |_| [] as [(usize, u32); 0]);
+
+    assert_eq!(distances[0][1], None);
+}
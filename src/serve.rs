@@ -0,0 +1,89 @@
+use crate::{days, solve, unavailable};
+use serde_json::{json, Value};
+use std::thread;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+// status and JSON body answering `method` on `url`
+fn respond(method: &Method, url: &str, body: String) -> (u16, Value) {
+    let segments = url.trim_matches('/').split('/').collect::<Vec<_>>();
+
+    match (method, segments.as_slice()) {
+        (Method::Get, ["days"]) => (200, json!({ "days": days() })),
+        (Method::Post, ["day", day]) => {
+            let Ok(day_number) = day.parse::<u32>() else {
+                return (400, json!({ "error": format!("invalid day {day}") }));
+            };
+
+            if !days().contains(&day_number) {
+                return (
+                    404,
+                    json!({ "day": day_number, "error": unavailable(day_number) }),
+                );
+            }
+
+            match solve(day_number, body) {
+                Ok((part1, part2, duration)) => (
+                    200,
+                    json!({
+                        "day": day_number,
+                        "part1": part1,
+                        "part2": part2,
+                        "duration_ms": duration.as_secs_f64() * 1e3,
+                    }),
+                ),
+                Err(error) => (422, json!({ "day": day_number, "error": error })),
+            }
+        }
+        (_, ["days"] | ["day", _]) => (405, json!({ "error": format!("{method} not allowed") })),
+        _ => (404, json!({ "error": format!("no route {url}") })),
+    }
+}
+
+fn handle(mut request: Request) {
+    let mut body = String::new();
+    let (status, value) = match request.as_reader().read_to_string(&mut body) {
+        Ok(_) => respond(request.method(), request.url(), body),
+        Err(e) => (400, json!({ "error": format!("unreadable input: {e}") })),
+    };
+    let header = Header::from_bytes("Content-Type", "application/json").unwrap();
+    let response = Response::from_string(value.to_string())
+        .with_status_code(status)
+        .with_header(header);
+
+    if let Err(e) = request.respond(response) {
+        eprintln!("cannot respond: {e}");
+    }
+}
+
+/// Serves the registered days on `address`: `GET /days` lists them and
+/// `POST /day/{n}` resolves day `n` on the posted input. A worker per core
+/// answers the requests, the others wait for one to be free.
+pub fn serve(address: &str) {
+    let server =
+        Server::http(address).unwrap_or_else(|e| panic!("cannot listen on {address}: {e}"));
+    let workers = thread::available_parallelism().map_or(1, |n| n.get());
+
+    eprintln!(
+        "listening on http://{}, answering {workers} requests at a time",
+        server.server_addr()
+    );
+
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                for request in server.incoming_requests() {
+                    handle(request);
+                }
+            });
+        }
+    });
+}
+
+#[test]
+fn check_respond() {
+    assert_eq!(respond(&Method::Get, "/days", String::new()).0, 200);
+    assert_eq!(respond(&Method::Post, "/day/x", String::new()).0, 400);
+    assert_eq!(respond(&Method::Post, "/day/99", String::new()).0, 404);
+    assert_eq!(respond(&Method::Get, "/day/1", String::new()).0, 405);
+    assert_eq!(respond(&Method::Get, "/", String::new()).0, 404);
+}
@@ -0,0 +1,280 @@
+use crate::{is_compiled_out, normalize, parse_day_number, read_raw_input, unavailable};
+use std::io::{self, BufRead, Write};
+
+pub trait Simulation {
+    /// Advances the simulation by one tick, returns false once it is over.
+    fn step(&mut self) -> bool;
+
+    /// Draws the current state.
+    fn render(&self) -> String;
+
+    /// Named values describing the current state, used by the step-through
+    /// mode to print a status line and to evaluate `until` conditions.
+    fn counters(&self) -> Vec<(&'static str, i64)> {
+        vec![]
+    }
+
+    /// Steps until `condition` holds, returns false if the simulation is over
+    /// before that.
+    fn run_until<F>(&mut self, mut condition: F) -> bool
+    where
+        F: FnMut(&Self) -> bool,
+        Self: Sized,
+    {
+        while !condition(self) {
+            if !self.step() {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+pub struct Simulator {
+    day_filename: &'static str,
+    new: fn(&str) -> Box<dyn Simulation>,
+}
+
+impl Simulator {
+    pub const fn new(day_filename: &'static str, new: fn(&str) -> Box<dyn Simulation>) -> Self {
+        Simulator { day_filename, new }
+    }
+
+    pub(crate) fn parse_number(&self) -> u32 {
+        parse_day_number(self.day_filename)
+    }
+
+    fn start(&self) -> Box<dyn Simulation> {
+        let day_number = self.parse_number();
+        let input = normalize::normalize(read_raw_input(day_number).unwrap());
+
+        (self.new)(&input)
+    }
+}
+
+inventory::collect!(Simulator);
+
+#[derive(Debug, PartialEq)]
+enum Comparison {
+    Equal,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+}
+
+#[derive(Debug, PartialEq)]
+struct Condition {
+    name: String,
+    comparison: Comparison,
+    value: i64,
+}
+
+impl Condition {
+    fn parse(s: &str) -> Option<Self> {
+        let operators = [
+            (">=", Comparison::GreaterEqual),
+            ("<=", Comparison::LessEqual),
+            ("=", Comparison::Equal),
+            (">", Comparison::Greater),
+            ("<", Comparison::Less),
+        ];
+
+        for (op, comparison) in operators {
+            if let Some((name, value)) = s.split_once(op) {
+                return Some(Condition {
+                    name: name.trim().to_string(),
+                    comparison,
+                    value: value.trim().parse().ok()?,
+                });
+            }
+        }
+        None
+    }
+
+    fn matches(&self, counters: &[(&'static str, i64)]) -> Option<bool> {
+        let (_, value) = counters.iter().find(|(name, _)| *name == self.name)?;
+
+        Some(match self.comparison {
+            Comparison::Equal => *value == self.value,
+            Comparison::Less => *value < self.value,
+            Comparison::LessEqual => *value <= self.value,
+            Comparison::Greater => *value > self.value,
+            Comparison::GreaterEqual => *value >= self.value,
+        })
+    }
+}
+
+#[test]
+fn check_condition() {
+    let counters = [("rocks", 12), ("height", 30)];
+
+    assert_eq!(
+        Condition::parse("rocks>=12"),
+        Some(Condition {
+            name: "rocks".to_string(),
+            comparison: Comparison::GreaterEqual,
+            value: 12
+        })
+    );
+    assert_eq!(Condition::parse("rocks"), None);
+    assert_eq!(Condition::parse("rocks=a"), None);
+
+    assert_eq!(
+        Condition::parse("rocks = 12").unwrap().matches(&counters),
+        Some(true)
+    );
+    assert_eq!(
+        Condition::parse("height<30").unwrap().matches(&counters),
+        Some(false)
+    );
+    assert_eq!(
+        Condition::parse("height>29").unwrap().matches(&counters),
+        Some(true)
+    );
+    assert_eq!(Condition::parse("sand=1").unwrap().matches(&counters), None);
+}
+
+const HELP: &str = "commands:
+  [n] [N]       step forward N ticks (default 1)
+  g N           go to tick N
+  u NAME<op>V   run until a counter matches, <op> is one of = < <= > >=
+  e             run until the end
+  r             restart
+  h             this help
+  q             quit";
+
+struct Stepper<'a> {
+    simulator: &'a Simulator,
+    simulation: Box<dyn Simulation>,
+    tick: usize,
+    over: bool,
+}
+
+impl<'a> Stepper<'a> {
+    fn new(simulator: &'a Simulator) -> Self {
+        Stepper {
+            simulator,
+            simulation: simulator.start(),
+            tick: 0,
+            over: false,
+        }
+    }
+
+    fn restart(&mut self) {
+        *self = Stepper::new(self.simulator);
+    }
+
+    fn step(&mut self) -> bool {
+        if !self.over {
+            self.over = !self.simulation.step();
+
+            if !self.over {
+                self.tick += 1;
+            }
+        }
+        !self.over
+    }
+
+    fn forward(&mut self, count: usize) {
+        for _ in 0..count {
+            if !self.step() {
+                break;
+            }
+        }
+    }
+
+    fn goto(&mut self, tick: usize) {
+        if tick < self.tick {
+            self.restart();
+        }
+        self.forward(tick - self.tick);
+    }
+
+    fn until(&mut self, condition: &Condition) -> Result<(), String> {
+        loop {
+            match condition.matches(&self.simulation.counters()) {
+                None => return Err(format!("unknown counter {}", condition.name)),
+                Some(true) => return Ok(()),
+                Some(false) if !self.step() => return Ok(()),
+                Some(false) => (),
+            }
+        }
+    }
+
+    fn print(&self) {
+        let status = self
+            .simulation
+            .counters()
+            .iter()
+            .map(|(name, value)| format!(" {name}={value}"))
+            .collect::<String>();
+        let over = if self.over { " (over)" } else { "" };
+
+        println!("{}", self.simulation.render());
+        println!("tick {}:{status}{over}", self.tick);
+    }
+
+    fn execute(&mut self, command: &str) -> Result<bool, String> {
+        let (name, arg) = command.split_once(' ').unwrap_or((command, ""));
+        let arg = arg.trim();
+        let number = || arg.parse::<usize>().map_err(|e| format!("{arg}: {e}"));
+
+        match name {
+            "" => self.forward(1),
+            "n" if arg.is_empty() => self.forward(1),
+            "n" => self.forward(number()?),
+            "g" => self.goto(number()?),
+            "u" => self.until(&Condition::parse(arg).ok_or(format!("bad condition {arg}"))?)?,
+            "e" => while self.step() {},
+            "r" => self.restart(),
+            "h" => {
+                println!("{HELP}");
+                return Ok(true);
+            }
+            "q" => return Ok(false),
+            _ => {
+                let count = name.parse::<usize>().map_err(|_| HELP.to_string())?;
+
+                self.forward(count);
+            }
+        }
+        self.print();
+        Ok(true)
+    }
+}
+
+/// Interactive step-through of a simulation day, commands are read on stdin.
+pub fn step(day_number: u32) {
+    let Some(simulator) = inventory::iter::<Simulator>
+        .into_iter()
+        .find(|s| s.parse_number() == day_number)
+    else {
+        if is_compiled_out(day_number) {
+            println!("{}", unavailable(day_number));
+        } else {
+            println!("day{day_number:0>2} has no simulation");
+        }
+        return;
+    };
+    let mut stepper = Stepper::new(simulator);
+
+    stepper.print();
+
+    loop {
+        print!("> ");
+        io::stdout().flush().unwrap();
+
+        let mut command = String::new();
+
+        if io::stdin().lock().read_line(&mut command).unwrap() == 0 {
+            break;
+        }
+
+        match stepper.execute(command.trim()) {
+            Ok(true) => (),
+            Ok(false) => break,
+            Err(e) => println!("{e}"),
+        }
+    }
+}
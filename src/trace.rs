@@ -0,0 +1,238 @@
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static NEXT_THREAD: AtomicUsize = AtomicUsize::new(1);
+static EVENTS: Mutex<Vec<Event>> = Mutex::new(vec![]);
+static ORIGIN: OnceLock<Instant> = OnceLock::new();
+
+struct Frame {
+    name: Cow<'static, str>,
+    // path of the parent span when it is on another thread
+    prefix: String,
+    start: Instant,
+    children: Duration,
+}
+
+thread_local! {
+    static THREAD: Cell<usize> = const { Cell::new(0) };
+    static STACK: RefCell<Vec<Frame>> = const { RefCell::new(vec![]) };
+}
+
+struct Event {
+    name: Cow<'static, str>,
+    stack: String,
+    thread: usize,
+    start: Duration,
+    duration: Duration,
+    self_duration: Duration,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    /// Chrome trace-event JSON, for chrome://tracing or Perfetto
+    Chrome,
+    /// folded stacks weighted by self time in microseconds, for flamegraph.pl
+    Folded,
+}
+
+/// Starts recording the spans.
+pub fn enable() {
+    ORIGIN.get_or_init(Instant::now);
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+fn thread_id() -> usize {
+    THREAD.with(|t| {
+        if t.get() == 0 {
+            t.set(NEXT_THREAD.fetch_add(1, Ordering::Relaxed));
+        }
+        t.get()
+    })
+}
+
+/// Recorded from its creation until it is dropped, nested in the spans still
+/// open on the same thread.
+pub struct Span {
+    active: bool,
+}
+
+pub fn span<N>(name: N) -> Span
+where
+    N: Into<Cow<'static, str>>,
+{
+    span_in("", name)
+}
+
+/// Same as `span` but nested in `parent`, a `path` taken on another thread.
+/// This is how work sent to rayon stays under the span that spawned it.
+pub fn span_in<N>(parent: &str, name: N) -> Span
+where
+    N: Into<Cow<'static, str>>,
+{
+    if !is_enabled() {
+        return Span { active: false };
+    }
+
+    STACK.with(|s| {
+        s.borrow_mut().push(Frame {
+            name: name.into(),
+            prefix: parent.to_string(),
+            start: Instant::now(),
+            children: Duration::ZERO,
+        })
+    });
+    Span { active: true }
+}
+
+fn stack_path(stack: &[Frame]) -> String {
+    stack
+        .iter()
+        .flat_map(|f| [f.prefix.as_str(), f.name.as_ref()])
+        .filter(|n| !n.is_empty())
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+/// Path of the spans currently open on this thread.
+pub fn path() -> String {
+    if !is_enabled() {
+        return String::new();
+    }
+
+    STACK.with(|s| stack_path(&s.borrow()))
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        if !self.active {
+            return;
+        }
+
+        let event = STACK.with(|s| {
+            let mut stack = s.borrow_mut();
+            let path = stack_path(&stack);
+            let frame = stack.pop().unwrap();
+            let duration = frame.start.elapsed();
+
+            if let Some(parent) = stack.last_mut() {
+                parent.children += duration;
+            }
+
+            Event {
+                name: frame.name,
+                stack: path,
+                thread: thread_id(),
+                start: frame.start.duration_since(*ORIGIN.get().unwrap()),
+                duration,
+                self_duration: duration.saturating_sub(frame.children),
+            }
+        });
+
+        EVENTS.lock().unwrap().push(event);
+    }
+}
+
+fn write_chrome<W: Write>(out: &mut W, events: &[Event]) -> io::Result<()> {
+    writeln!(out, "[")?;
+    for (i, e) in events.iter().enumerate() {
+        let separator = if i + 1 < events.len() { "," } else { "" };
+
+        writeln!(
+            out,
+            r#"{{"name":"{}","ph":"X","ts":{:.3},"dur":{:.3},"pid":1,"tid":{}}}{separator}"#,
+            e.name.replace('\\', "\\\\").replace('"', "\\\""),
+            e.start.as_secs_f64() * 1e6,
+            e.duration.as_secs_f64() * 1e6,
+            e.thread
+        )?;
+    }
+    writeln!(out, "]")
+}
+
+fn write_folded<W: Write>(out: &mut W, events: &[Event]) -> io::Result<()> {
+    let mut stacks: BTreeMap<&str, Duration> = BTreeMap::new();
+
+    for e in events {
+        *stacks.entry(&e.stack).or_default() += e.self_duration;
+    }
+
+    for (stack, duration) in stacks {
+        writeln!(out, "{stack} {}", duration.as_micros())?;
+    }
+    Ok(())
+}
+
+/// Writes the spans recorded so far into `path`.
+pub fn write<P>(path: P, format: Format) -> io::Result<()>
+where
+    P: AsRef<Path>,
+{
+    let events = EVENTS.lock().unwrap();
+    let mut out = BufWriter::new(File::create(path)?);
+
+    match format {
+        Format::Chrome => write_chrome(&mut out, &events),
+        Format::Folded => write_folded(&mut out, &events),
+    }
+}
+
+#[test]
+fn check_span() {
+    enable();
+
+    {
+        let _outer = span("check_outer");
+        let _inner = span("check_inner");
+    }
+
+    let events = EVENTS.lock().unwrap();
+    let inner = events.iter().find(|e| e.name == "check_inner").unwrap();
+    let outer = events.iter().find(|e| e.name == "check_outer").unwrap();
+
+    assert_eq!(inner.stack, "check_outer;check_inner");
+    assert_eq!(outer.stack, "check_outer");
+    assert!(outer.duration >= inner.duration);
+    assert_eq!(inner.thread, outer.thread);
+
+    let outer = outer.thread;
+
+    drop(events);
+
+    let parent = {
+        let _outer = span("check_parent");
+
+        path()
+    };
+
+    std::thread::spawn(move || {
+        let _span = span_in(&parent, "check_child");
+    })
+    .join()
+    .unwrap();
+
+    let events = EVENTS.lock().unwrap();
+    let child = events.iter().find(|e| e.name == "check_child").unwrap();
+
+    assert_eq!(child.stack, "check_parent;check_child");
+    assert_ne!(child.thread, outer);
+
+    let mut folded = vec![];
+
+    write_folded(&mut folded, &events).unwrap();
+    assert!(String::from_utf8(folded)
+        .unwrap()
+        .contains("check_outer;check_inner "));
+}
@@ -0,0 +1,57 @@
+use crate::{all_days, answers_path, input_path, resolve};
+use std::fs;
+use std::panic;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+fn modified(path: &str) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn stamps(day_number: u32) -> (Option<SystemTime>, Option<SystemTime>) {
+    (
+        modified(&input_path(day_number)),
+        modified(&answers_path(day_number)),
+    )
+}
+
+fn resolve_watched(days: &[u32]) {
+    // a file caught while being written may well make a day panic, report it
+    // and keep watching
+    if panic::catch_unwind(|| resolve(days)).is_err() {
+        println!("Failed, waiting for the next change");
+    }
+}
+
+/// Resolves `days` (all of them if empty) each time one of their input or
+/// answers files changes. The files are polled so this never returns.
+pub fn watch(days: &[u32]) {
+    let days = if days.is_empty() {
+        all_days().iter().map(|d| d.parse_number()).collect()
+    } else {
+        days.to_vec()
+    };
+    let mut last = days.iter().map(|&d| stamps(d)).collect::<Vec<_>>();
+
+    resolve_watched(&days);
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+
+        let current = days.iter().map(|&d| stamps(d)).collect::<Vec<_>>();
+        let changed = days
+            .iter()
+            .zip(current.iter().zip(&last))
+            .filter_map(|(&d, (c, l))| if c != l { Some(d) } else { None })
+            .collect::<Vec<_>>();
+
+        last = current;
+
+        if !changed.is_empty() {
+            println!();
+            resolve_watched(&changed);
+        }
+    }
+}